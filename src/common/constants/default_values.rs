@@ -26,4 +26,49 @@ impl DefaultValues<'static> {
     pub const BUY_INTERRUPTER_KEYWORD: &'static str = "by";
 
     pub const SELL_INTERRUPTER_KEYWORD: &'static str = "sl";
+
+    // the [gas_price] keyword to compute the gas price from the network gas price on every attempt
+    // and the [gas_limit] keyword to estimate the gas limit of the tx
+    pub const AUTO_KEYWORD: &'static str = "auto";
+
+    pub const TRADE_ACTUATOR_POLLING_INTERVAL_IN_MS: u64 = 500;
+
+    pub const PAIR_ADDRESS_POLLING_INTERVAL_IN_MS: u64 = 500;
+
+    // the router swap fee (0.25%) expressed as a fraction of the amount in
+    pub const ROUTER_SWAP_FEE_NUMERATOR: u64 = 9975;
+
+    pub const ROUTER_SWAP_FEE_DENOMINATOR: u64 = 10000;
+
+    // the number of simulated swaps used to measure the amounts received by the Buy and the Sell of the honeypot check
+    pub const HONEYPOT_CHECK_SEARCH_STEPS: u32 = 8;
+
+    // the number of storage slots searched for the balances and the allowances mappings of the token by the honeypot check
+    pub const HONEYPOT_CHECK_STORAGE_SLOTS: u64 = 32;
+
+    pub const HONEYPOT_MAX_LOSS_PERC: u8 = 50;
+
+    // the number of simulated swaps used to measure the amount received by the transfer tax detection
+    pub const TAX_DETECTION_SEARCH_STEPS: u32 = 16;
+
+    // the rolling window of the daily budgets
+    pub const BUDGET_ROLLING_WINDOW_IN_SECS: i64 = 86400;
+
+    // the gas price increase of the emergency Sell triggered by the rug pull watcher
+    pub const RUG_PULL_PERC_INCREASE_GAS_PRICE: u32 = 50;
+
+    pub const PENDING_SWAP_TX_POLLING_INTERVAL_IN_MS: u64 = 500;
+
+    // the nodes accept a replacement tx only if it pays at least 10% more gas than the pending tx
+    pub const REPLACEMENT_TX_MIN_PERC_INCREASE_GAS_PRICE: u64 = 10;
+
+    // the safety margin added to the estimated gas of the 'auto' gas limit
+    pub const GAS_LIMIT_MARGIN_PERC: u32 = 20;
+
+    // the default gas price of the cancel tx is increased by 25% over the network gas price
+    // or the minimum replacement gas price of the pending tx, whichever is higher
+    pub const CANCEL_TX_PERC_INCREASE_GAS_PRICE: u64 = 25;
+
+    // the gas used by a transfer of the native coin
+    pub const NATIVE_TRANSFER_GAS_LIMIT: u64 = 21000;
 }
@@ -13,6 +13,8 @@ pub struct FilePaths<'a> {
 impl FilePaths<'static> {
     pub const CONFIG: &'static str = "./config.yaml";
     pub const SECRETS: &'static str = "./secrets.yaml";
+    pub const TAKE_PROFIT_LADDERS: &'static str = "./take-profit-ladders";
+    pub const LEDGERS: &'static str = "./ledgers";
     pub const LOG: &'static str = if Qenv::IS_RELEASE {
         "./logs/logging-release.log"
     } else {
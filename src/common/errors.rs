@@ -25,6 +25,27 @@ pub enum TradingError<'a> {
 
     #[error("a trading error occured while trying to swap the token: {0:?}")]
     SwapToken(&'a str),
+
+    #[error("a trading error occured while trying to find the swap route: {0:?}")]
+    Route(&'a str),
+
+    #[error("a trading error occured while checking the token for a honeypot: {0:?}")]
+    Honeypot(&'a str),
+
+    #[error("a trading error occured while trying to detect the transfer tax of the token: {0:?}")]
+    TaxDetection(&'a str),
+
+    #[error("a trading error occured while trying to compute the price impact of the swap: {0:?}")]
+    PriceImpact(&'a str),
+
+    #[error("a trading error occured while scanning the token contract for risks: {0:?}")]
+    RiskScan(&'a str),
+
+    #[error("a trading error occured while checking the budget: {0:?}")]
+    Budget(&'a str),
+
+    #[error("a trading error occured while trying to carry out the trades: {0:?}")]
+    Trades(&'a str),
 }
 
 #[derive(Error, Debug)]
@@ -36,6 +57,21 @@ pub enum OrderBookError<'a> {
     Sell(&'a str),
 }
 
+#[derive(Error, Debug)]
+pub enum TransferError<'a> {
+    #[error("a transfer error occured while trying to check the token balance: {0:?}")]
+    InsufficientBalance(&'a str),
+
+    #[error("a transfer error occured while trying to send the token: {0:?}")]
+    SendToken(&'a str),
+}
+
+#[derive(Error, Debug)]
+pub enum CancelTxError<'a> {
+    #[error("a cancel tx error occured while trying to cancel the pending tx: {0:?}")]
+    SendTx(&'a str),
+}
+
 #[derive(Error, Debug)]
 pub enum QuantError<'a> {
     #[error("a utils error has occured: {0:?}")]
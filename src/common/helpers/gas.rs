@@ -1,6 +1,8 @@
-use crate::common::utils::ether::decimals_to_gwei;
+use crate::common::utils::ether::{decimals_to_gwei, gwei_to_human_display};
 use crate::common::utils::f256::{percentage_of_f256, to_f256};
+use crate::common::utils::u256::percentage_of_u256;
 use ethers::abi::ethereum_types::U256;
+use min_max::{max, min};
 use qd::Quad;
 use std::ops::{Add, Mul};
 
@@ -31,3 +33,117 @@ pub fn gas_price_calc(gas_price: &str, gas_limit: &str) -> anyhow::Result<Quad>
 
     Ok(r)
 }
+
+// the 'auto' gas price is [gas_price_multiplier_perc] percent of the network gas price
+// it is kept between [min_gas_price_gwei] and [max_gas_price_gwei]
+pub fn auto_gas_price(
+    network_gas_price_gwei: U256,
+    gas_price_multiplier_perc: Option<u32>,
+    min_gas_price_gwei: Option<U256>,
+    max_gas_price_gwei: Option<U256>,
+) -> (Quad, U256) {
+    let mut gas_price_gwei = match gas_price_multiplier_perc {
+        None => network_gas_price_gwei,
+        Some(p) => percentage_of_u256(network_gas_price_gwei, p as u64),
+    };
+
+    if let Some(m) = min_gas_price_gwei {
+        gas_price_gwei = max!(gas_price_gwei, m);
+    }
+
+    if let Some(m) = max_gas_price_gwei {
+        gas_price_gwei = min!(gas_price_gwei, m);
+    }
+
+    (gwei_to_human_display(gas_price_gwei), gas_price_gwei)
+}
+
+// the 'auto' gas limit is the estimated gas plus [gas_limit_margin_perc] percent
+// it is capped at [max_gas_limit_u256]
+pub fn gas_limit_with_margin(
+    estimated_gas_u256: U256,
+    gas_limit_margin_perc: u32,
+    max_gas_limit_u256: Option<U256>,
+) -> U256 {
+    let gas_limit_u256 = estimated_gas_u256.add(percentage_of_u256(
+        estimated_gas_u256,
+        gas_limit_margin_perc as u64,
+    ));
+
+    match max_gas_limit_u256 {
+        None => gas_limit_u256,
+        Some(m) => min!(gas_limit_u256, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gwei(d: u64) -> U256 {
+        U256::from(d).mul(U256::exp10(9))
+    }
+
+    #[test]
+    fn auto_gas_price_is_the_network_gas_price_without_a_multiplier() {
+        let (_, gas_price_gwei) = auto_gas_price(gwei(5), None, None, None);
+
+        assert_eq!(gas_price_gwei, gwei(5));
+    }
+
+    #[test]
+    fn auto_gas_price_is_multiplied_by_the_multiplier() {
+        let (_, gas_price_gwei) = auto_gas_price(gwei(5), Some(120), None, None);
+
+        assert_eq!(gas_price_gwei, gwei(6));
+
+        let (_, gas_price_gwei) = auto_gas_price(gwei(10), Some(50), None, None);
+
+        assert_eq!(gas_price_gwei, gwei(5));
+    }
+
+    #[test]
+    fn auto_gas_price_is_raised_to_the_min_gas_price() {
+        let (_, gas_price_gwei) = auto_gas_price(gwei(5), Some(100), Some(gwei(7)), Some(gwei(10)));
+
+        assert_eq!(gas_price_gwei, gwei(7));
+    }
+
+    #[test]
+    fn auto_gas_price_is_capped_at_the_max_gas_price() {
+        let (_, gas_price_gwei) = auto_gas_price(gwei(5), Some(300), Some(gwei(7)), Some(gwei(10)));
+
+        assert_eq!(gas_price_gwei, gwei(10));
+    }
+
+    #[test]
+    fn auto_gas_price_within_the_bounds_is_kept() {
+        let (_, gas_price_gwei) = auto_gas_price(gwei(5), Some(160), Some(gwei(7)), Some(gwei(10)));
+
+        assert_eq!(gas_price_gwei, gwei(8));
+    }
+
+    #[test]
+    fn gas_limit_with_margin_adds_the_margin_to_the_estimated_gas() {
+        assert_eq!(
+            gas_limit_with_margin(U256::from(200_000), 25, None),
+            U256::from(250_000)
+        );
+        assert_eq!(
+            gas_limit_with_margin(U256::from(200_000), 0, None),
+            U256::from(200_000)
+        );
+    }
+
+    #[test]
+    fn gas_limit_with_margin_is_capped_at_the_max_gas_limit() {
+        assert_eq!(
+            gas_limit_with_margin(U256::from(200_000), 25, Some(U256::from(220_000))),
+            U256::from(220_000)
+        );
+        assert_eq!(
+            gas_limit_with_margin(U256::from(200_000), 25, Some(U256::from(300_000))),
+            U256::from(250_000)
+        );
+    }
+}
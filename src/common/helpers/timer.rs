@@ -1,6 +1,9 @@
 use crate::common::models::trade_scheme::TradeName;
 use crate::common::strings::trim_newline;
-use cancellable_timer::{Canceller, Timer};
+use std::sync::Once;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::{Receiver, Sender};
 use tokio::time::sleep;
 use tokio::time::Duration;
 
@@ -8,68 +11,117 @@ pub async fn tokio_sleep(time_ms: u64) {
     sleep(Duration::from_millis(time_ms)).await
 }
 
-async fn read_from_stdin(
-    interrupter_keyword: String,
-    trade_name: TradeName,
-    timer_canceller: Canceller,
-) -> async_std::io::Result<()> {
-    'read_loop: loop {
-        log::info!(
-            r#"type "{}" and press enter to '{}' immediately: "#,
-            interrupter_keyword,
-            trade_name
-        );
+/// a single stdin reader shared by all the trades.
+/// every line typed in is broadcast to all the trades waiting on an interrupter keyword,
+/// so a keyword interrupts every trade waiting on it
+#[derive(Debug)]
+pub struct StdinInterrupter {
+    sender: Sender<String>,
 
-        let stdin = async_std::io::stdin();
-        let mut input_line = String::new();
+    // the stdin is read only once a trade waits on a keyword
+    // reading it any earlier would take over the inputs of the interactive prompts
+    reader_start: Once,
+}
 
-        let r = stdin.read_line(&mut input_line);
-        let n = r.await?;
+impl Default for StdinInterrupter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StdinInterrupter {
+    // the number of lines kept for the trades which haven't received them yet
+    const CHANNEL_CAPACITY: usize = 16;
+
+    pub fn new() -> StdinInterrupter {
+        let (sender, _) = broadcast::channel(Self::CHANNEL_CAPACITY);
+
+        StdinInterrupter {
+            sender,
+            reader_start: Once::new(),
+        }
+    }
 
-        input_line = trim_newline(input_line);
+    async fn read_from_stdin(sender: Sender<String>) -> async_std::io::Result<()> {
+        loop {
+            let stdin = async_std::io::stdin();
+            let mut input_line = String::new();
 
-        log::debug!("received the input line: {}", input_line);
+            let n = stdin.read_line(&mut input_line).await?;
 
-        if input_line.eq(&interrupter_keyword) {
-            log::debug!(
-                "interrupter keyword received, cancelling the timer (if not cancelled already)..."
-            );
+            // the stdin was closed
+            if n == 0 {
+                return Ok(());
+            }
 
-            timer_canceller.cancel()?;
+            input_line = trim_newline(input_line);
 
-            break 'read_loop;
-        } else {
-            log::debug!(
-                r#"the received interrupter keyword ({}) does NOT match, ({}). trying again..."#,
-                input_line,
-                interrupter_keyword
-            );
+            log::debug!("received the input line: {}", input_line);
+
+            // there is no trade waiting on a keyword
+            let _ = sender.send(input_line);
         }
     }
 
-    Ok(())
-}
+    fn subscribe(&self) -> Receiver<String> {
+        // the receiver is created before the reader starts so that it doesn't miss the first line
+        let receiver = self.sender.subscribe();
 
-pub async fn interruptable_sleep(
-    interrupter_keyword: String,
-    trade_name: TradeName,
-    sleep_for_ms: u64,
-) -> anyhow::Result<()> {
-    let (mut timer, timer_canceller) = Timer::new2()?;
+        self.reader_start.call_once(|| {
+            let sender = self.sender.clone();
 
-    let c1 = async_std::task::spawn(read_from_stdin(
-        interrupter_keyword,
-        trade_name,
-        timer_canceller,
-    ));
+            tokio::spawn(async move {
+                if let Err(e) = Self::read_from_stdin(sender).await {
+                    log::error!("unable to read from the stdin: {:?}", e);
+                }
+            });
+        });
 
-    let t = timer.sleep(Duration::from_millis(sleep_for_ms));
+        receiver
+    }
 
-    if t.is_err() {
-        // do nothing there
+    async fn wait_for_keyword(mut receiver: Receiver<String>, interrupter_keyword: &str) {
+        loop {
+            match receiver.recv().await {
+                Ok(input_line) => {
+                    if input_line.eq(interrupter_keyword) {
+                        log::debug!("interrupter keyword received, waking up from the sleep...");
+
+                        return;
+                    }
+
+                    log::debug!(
+                        r#"the received interrupter keyword ({}) does NOT match, ({}). trying again..."#,
+                        input_line,
+                        interrupter_keyword
+                    );
+                }
+                Err(RecvError::Lagged(_)) => {}
+                // the stdin reader has stopped, the sleep can't be interrupted anymore
+                Err(RecvError::Closed) => std::future::pending::<()>().await,
+            }
+        }
     }
 
-    async_std::task::block_on(c1.cancel());
+    // sleep for [sleep_for_ms] unless [interrupter_keyword] is typed in
+    // the sleep doesn't block the other trades running at the same time
+    pub async fn interruptable_sleep(
+        &self,
+        interrupter_keyword: &str,
+        trade_name: TradeName,
+        sleep_for_ms: u64,
+    ) {
+        let receiver = self.subscribe();
+
+        log::info!(
+            r#"type "{}" and press enter to '{}' immediately: "#,
+            interrupter_keyword,
+            trade_name
+        );
 
-    Ok(())
+        tokio::select! {
+            _ = tokio_sleep(sleep_for_ms) => {}
+            _ = Self::wait_for_keyword(receiver, interrupter_keyword) => {}
+        }
+    }
 }
@@ -3,3 +3,4 @@ pub mod trade_scheme;
 pub mod scheme_helpers;
 pub mod secrets;
 pub mod token_transfer_scheme;
+pub mod ledger;
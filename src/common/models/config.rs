@@ -60,6 +60,8 @@ pub struct NetworkEntity {
     pub native_token_symbol: String,
 
     pub check_gas_fees: bool,
+
+    pub budget: Option<Budget>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -114,6 +116,39 @@ pub struct Feature {
     pub token_transfers: Option<Vec<TokenTransfers>>,
 }
 
+/// the spending limits of the account in a network.
+/// the actual spends are recorded in the ledger file of the network
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Budget {
+    pub max_gas_spend_per_run: Option<String>,
+
+    pub max_gas_spend_per_day: Option<String>,
+
+    pub token_budgets: Option<Vec<TokenBudgets>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TokenBudgets {
+    pub token_budget: TokenBudgetEntity,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TokenBudgetEntity {
+    pub token_contract: String,
+
+    pub token_symbol: String,
+
+    pub max_spend_per_run: Option<String>,
+
+    pub max_spend_per_day: Option<String>,
+}
+
+impl TokenBudgetEntity {
+    pub fn token_h160(&self) -> anyhow::Result<Address> {
+        Ok(Address::from_str(&*self.token_contract)?)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Providers {
     pub provider: ProviderEntity,
@@ -0,0 +1,204 @@
+use crate::common::constants::default_values::DefaultValues;
+use crate::common::constants::file_paths::FilePaths;
+use crate::common::models::config::NetworkName;
+use crate::common::utils::files::file::{create_directory, exists};
+use chrono::Utc;
+use ethers::abi::ethereum_types::{Address, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::ops::Add;
+
+/// the swaps carried out in a network.
+/// it is read by the later runs to enforce the daily budgets
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LedgerEntry {
+    /// unix timestamp in seconds
+    pub timestamp: i64,
+    pub account_h160: Address,
+    pub tx_hash: H256,
+    pub token_in_h160: Address,
+    pub amount_of_token_in_u256: U256,
+    pub gas_fees_u256: U256,
+}
+
+impl Ledger {
+    pub fn file_path(network_name: &NetworkName) -> String {
+        format!(
+            "{}/{}.yaml",
+            FilePaths::LEDGERS,
+            network_name.to_string().to_lowercase()
+        )
+    }
+
+    // the start of the rolling window of the daily budgets
+    pub fn window_start() -> i64 {
+        Utc::now().timestamp() - DefaultValues::BUDGET_ROLLING_WINDOW_IN_SECS
+    }
+
+    // returns an empty ledger if nothing was recorded yet
+    pub fn load(file_path: &str) -> anyhow::Result<Self> {
+        if !exists(file_path) {
+            return Ok(Self::default());
+        }
+
+        let f = File::open(file_path)?;
+        let ledger: Self = serde_yaml::from_reader(f)?;
+
+        Ok(ledger)
+    }
+
+    // the entries older than the rolling window are no longer required and are dropped
+    fn prune(&mut self, window_start: i64) {
+        self.entries.retain(|d| d.timestamp >= window_start);
+    }
+
+    pub fn record(file_path: &str, entry: LedgerEntry) -> anyhow::Result<()> {
+        let mut ledger = Self::load(file_path)?;
+
+        ledger.prune(Self::window_start());
+        ledger.entries.push(entry);
+
+        create_directory(FilePaths::LEDGERS)?;
+
+        let f = File::create(file_path)?;
+        serde_yaml::to_writer(f, &ledger)?;
+
+        Ok(())
+    }
+
+    pub fn token_in_spent_since(
+        &self,
+        account_h160: Address,
+        token_in_h160: Address,
+        since: i64,
+    ) -> U256 {
+        self.entries
+            .iter()
+            .filter(|d| {
+                d.timestamp >= since
+                    && d.account_h160 == account_h160
+                    && d.token_in_h160 == token_in_h160
+            })
+            .fold(U256::zero(), |acc, d| acc.add(d.amount_of_token_in_u256))
+    }
+
+    pub fn gas_spent_since(&self, account_h160: Address, since: i64) -> U256 {
+        self.entries
+            .iter()
+            .filter(|d| d.timestamp >= since && d.account_h160 == account_h160)
+            .fold(U256::zero(), |acc, d| acc.add(d.gas_fees_u256))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(
+        timestamp: i64,
+        account_h160: Address,
+        token_in_h160: Address,
+        amount_of_token_in: u64,
+        gas_fees: u64,
+    ) -> LedgerEntry {
+        LedgerEntry {
+            timestamp,
+            account_h160,
+            tx_hash: H256::zero(),
+            token_in_h160,
+            amount_of_token_in_u256: U256::from(amount_of_token_in),
+            gas_fees_u256: U256::from(gas_fees),
+        }
+    }
+
+    fn ledger() -> Ledger {
+        let account = Address::from_low_u64_be(10);
+        let another_account = Address::from_low_u64_be(11);
+        let wbnb = Address::from_low_u64_be(1);
+        let busd = Address::from_low_u64_be(2);
+
+        Ledger {
+            entries: vec![
+                entry(100, account, wbnb, 10, 1),
+                entry(200, account, wbnb, 20, 2),
+                entry(200, account, busd, 30, 3),
+                // an approval spends only the gas fees
+                entry(300, account, busd, 0, 4),
+                entry(300, another_account, wbnb, 40, 5),
+            ],
+        }
+    }
+
+    #[test]
+    fn token_in_spent_since_adds_up_the_token_in_within_the_window() {
+        let ledger = ledger();
+        let account = Address::from_low_u64_be(10);
+        let wbnb = Address::from_low_u64_be(1);
+        let busd = Address::from_low_u64_be(2);
+
+        assert_eq!(
+            ledger.token_in_spent_since(account, wbnb, 0),
+            U256::from(30)
+        );
+        assert_eq!(
+            ledger.token_in_spent_since(account, wbnb, 200),
+            U256::from(20)
+        );
+        assert_eq!(
+            ledger.token_in_spent_since(account, busd, 200),
+            U256::from(30)
+        );
+        assert_eq!(
+            ledger.token_in_spent_since(account, wbnb, 301),
+            U256::zero()
+        );
+    }
+
+    #[test]
+    fn gas_spent_since_adds_up_the_gas_fees_of_every_token_within_the_window() {
+        let ledger = ledger();
+        let account = Address::from_low_u64_be(10);
+
+        assert_eq!(ledger.gas_spent_since(account, 0), U256::from(10));
+        assert_eq!(ledger.gas_spent_since(account, 200), U256::from(9));
+        assert_eq!(ledger.gas_spent_since(account, 301), U256::zero());
+    }
+
+    #[test]
+    fn spends_of_another_account_are_not_counted() {
+        let ledger = ledger();
+        let another_account = Address::from_low_u64_be(11);
+        let wbnb = Address::from_low_u64_be(1);
+
+        assert_eq!(
+            ledger.token_in_spent_since(another_account, wbnb, 0),
+            U256::from(40)
+        );
+        assert_eq!(ledger.gas_spent_since(another_account, 0), U256::from(5));
+        assert_eq!(
+            ledger.token_in_spent_since(Address::from_low_u64_be(12), wbnb, 0),
+            U256::zero()
+        );
+    }
+
+    #[test]
+    fn prune_drops_the_entries_older_than_the_window() {
+        let mut ledger = ledger();
+
+        ledger.prune(200);
+
+        assert_eq!(
+            ledger
+                .entries
+                .iter()
+                .map(|d| d.timestamp)
+                .collect::<Vec<i64>>(),
+            vec![200, 200, 300, 300]
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use crate::common::utils::ether::{decimals_to_ethers, decimals_to_gwei};
+use crate::common::constants::default_values::DefaultValues;
+use crate::common::utils::ether::{decimals_to_ethers, decimals_to_gwei, decimals_to_units};
 use crate::common::utils::u256::ToU256Units;
 use ethers::abi::ethereum_types::U256;
 use ethers::abi::Address;
@@ -71,17 +72,32 @@ impl SchemeHelpers {
         }
     }
 
-    pub fn price_to_u256(value: &str) -> anyhow::Result<U256> {
-        let e = decimals_to_ethers(&value.to_owned())?;
+    pub fn price_to_u256_option(value: &Option<String>) -> anyhow::Result<Option<U256>> {
+        match value {
+            None => Ok(None),
+            Some(d) => {
+                let e = decimals_to_ethers(d)?;
+
+                Ok(Some(e))
+            }
+        }
+    }
+
+    // [decimals] is the 'decimals' of the ERC20 token the [value] belongs to
+    pub fn amount_to_u256(value: &str, decimals: u8) -> anyhow::Result<U256> {
+        let e = decimals_to_units(&value.to_owned(), decimals)?;
 
         Ok(e)
     }
 
-    pub fn price_to_u256_option(value: &Option<String>) -> anyhow::Result<Option<U256>> {
+    pub fn amount_to_u256_option(
+        value: &Option<String>,
+        decimals: u8,
+    ) -> anyhow::Result<Option<U256>> {
         match value {
             None => Ok(None),
             Some(d) => {
-                let e = decimals_to_ethers(d)?;
+                let e = decimals_to_units(d, decimals)?;
 
                 Ok(Some(e))
             }
@@ -95,6 +111,21 @@ impl SchemeHelpers {
         decimals_to_gwei(value)
     }
 
+    // [None] if the gas price is computed from the network gas price
+    pub fn gas_price_to_gwei_option(gas_price: &str) -> anyhow::Result<Option<U256>> {
+        if SchemeHelpers::is_auto_keyword(gas_price) {
+            return Ok(None);
+        }
+
+        Ok(Some(SchemeHelpers::decimals_price_to_gwei(
+            &gas_price.to_string(),
+        )?))
+    }
+
+    pub fn is_auto_keyword(value: &str) -> bool {
+        value.eq_ignore_ascii_case(DefaultValues::AUTO_KEYWORD)
+    }
+
     pub fn decimals_price_to_gwei_option<K>(value: &Option<K>) -> anyhow::Result<Option<U256>>
     where
         K: ToU256Units,
@@ -116,6 +147,23 @@ impl SchemeHelpers {
         }
     }
 
+    pub fn contracts_to_h160_option(
+        contracts: &Option<Vec<String>>,
+    ) -> anyhow::Result<Option<Vec<Address>>> {
+        match &contracts {
+            None => Ok(None),
+            Some(d) => {
+                let mut h160_list: Vec<Address> = vec![];
+
+                for c in d {
+                    h160_list.push(Address::from_str(&*c)?);
+                }
+
+                Ok(Some(h160_list))
+            }
+        }
+    }
+
     pub fn convert_to_u256_option(value: &Option<u64>) -> anyhow::Result<Option<U256>> {
         match value {
             None => Ok(None),
@@ -10,6 +10,8 @@ pub struct TokenTransfers {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TokenTransfer {
+    pub token_contract: String,
+    pub token_symbol: String,
     pub recipient_wallet_address: String,
     pub wallet_name: String,
     pub amount_of_tokens: String,
@@ -18,12 +20,16 @@ pub struct TokenTransfer {
 }
 
 impl TokenTransfer {
+    pub fn token_h160(&self) -> anyhow::Result<Address> {
+        SchemeHelpers::contract_to_h160(&self.token_contract)
+    }
+
     pub fn recipient_wallet_address_h160(&self) -> anyhow::Result<Address> {
         SchemeHelpers::contract_to_h160(&self.recipient_wallet_address)
     }
 
-    pub fn amount_of_token_u256(&self) -> anyhow::Result<U256> {
-        SchemeHelpers::price_to_u256(&self.amount_of_tokens)
+    pub fn amount_of_token_u256(&self, token_decimals: u8) -> anyhow::Result<U256> {
+        SchemeHelpers::amount_to_u256(&self.amount_of_tokens, token_decimals)
     }
 
     pub fn gas_price_gwei(&self) -> anyhow::Result<Option<U256>> {
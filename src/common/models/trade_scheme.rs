@@ -4,6 +4,7 @@ use ethers::abi::Address;
 use ethers::types::U256;
 use serde::{Deserialize, Serialize};
 
+use crate::common::constants::default_values::DefaultValues;
 use crate::common::models::scheme_helpers::SchemeHelpers;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -46,22 +47,44 @@ pub struct BuyScheme {
 
     pub amount_of_token_in: String,
 
+    pub amount_of_token_out: Option<String>,
+
     pub slippage: u8,
 
+    pub perc_increase_slippage: Option<u32>,
+
+    pub max_slippage: Option<u8>,
+
     pub gas_price: String,
 
-    pub gas_limit: u64,
+    pub gas_price_multiplier_perc: Option<u32>,
+
+    pub min_gas_price: Option<String>,
+
+    pub gas_limit: GasLimit,
+
+    pub gas_limit_margin_perc: Option<u32>,
+
+    pub max_gas_limit: Option<u64>,
 
     pub tx_timeout_in_ms: i64,
 
     pub min_liquidity_required: Option<String>,
 
+    pub max_price_impact_perc: Option<u8>,
+
+    pub abort_on_max_price_impact: Option<bool>,
+
     pub approve_gas_price: Option<String>,
 
-    pub approve_gas_limit: Option<u64>,
+    pub approve_gas_limit: Option<GasLimit>,
+
+    pub max_approve_gas_limit: Option<u64>,
 
     pub is_token_out_deflationary: bool,
 
+    pub auto_detect_tax: Option<bool>,
+
     pub perc_increase_gas_price: Option<u32>,
 
     pub max_gas_price: Option<String>,
@@ -77,6 +100,20 @@ pub struct BuyScheme {
     pub trade_at_price: Option<String>,
 
     pub hold_trade_above_price: Option<String>,
+
+    pub snipe_liquidity_from_mempool: Option<bool>,
+
+    pub honeypot_check: Option<bool>,
+
+    pub honeypot_max_loss_perc: Option<u8>,
+
+    pub risk_scan: Option<bool>,
+
+    pub max_risk_score: Option<u8>,
+
+    pub path: Option<Vec<String>>,
+
+    pub auto_route: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -96,20 +133,40 @@ pub struct SellScheme {
 
     pub slippage: u8,
 
+    pub perc_increase_slippage: Option<u32>,
+
+    pub max_slippage: Option<u8>,
+
     pub gas_price: String,
 
-    pub gas_limit: u64,
+    pub gas_price_multiplier_perc: Option<u32>,
+
+    pub min_gas_price: Option<String>,
+
+    pub gas_limit: GasLimit,
+
+    pub gas_limit_margin_perc: Option<u32>,
+
+    pub max_gas_limit: Option<u64>,
 
     pub tx_timeout_in_ms: i64,
 
     pub min_liquidity_required: Option<String>,
 
+    pub max_price_impact_perc: Option<u8>,
+
+    pub abort_on_max_price_impact: Option<bool>,
+
     pub approve_gas_price: Option<String>,
 
-    pub approve_gas_limit: Option<u64>,
+    pub approve_gas_limit: Option<GasLimit>,
+
+    pub max_approve_gas_limit: Option<u64>,
 
     pub is_token_out_deflationary: bool,
 
+    pub auto_detect_tax: Option<bool>,
+
     pub perc_of_token_in: Option<u8>,
 
     pub perc_increase_gas_price: Option<u32>,
@@ -131,31 +188,87 @@ pub struct SellScheme {
     pub trade_at_profit_perc: Option<u8>,
 
     pub hold_trade_below_profit_perc: Option<u8>,
+
+    pub trailing_stop_loss_perc: Option<u8>,
+
+    pub stop_loss_floor_price: Option<String>,
+
+    pub take_profit_ladder: Option<Vec<TakeProfitStep>>,
+
+    pub rug_pull_liquidity_drop_perc: Option<u8>,
+
+    pub rug_pull_perc_increase_gas_price: Option<u32>,
+
+    pub path: Option<Vec<String>>,
+
+    pub auto_route: Option<bool>,
 }
 
 pub trait TradeSchemeVariant {
-    fn gas_limit_u256(&self) -> anyhow::Result<U256>;
+    // [None] if the gas limit is 'auto'
+    fn gas_limit_u256(&self) -> anyhow::Result<Option<U256>>;
+
+    fn max_gas_limit_u256(&self) -> anyhow::Result<Option<U256>>;
+
+    fn gas_limit_margin_perc(&self) -> u32;
 
-    fn gas_price_gwei(&self) -> anyhow::Result<U256>;
+    // [None] if the gas price is 'auto'
+    fn gas_price_gwei(&self) -> anyhow::Result<Option<U256>>;
+
+    fn is_auto_gas_price_active(&self) -> bool;
+
+    fn min_gas_price_gwei(&self) -> anyhow::Result<Option<U256>>;
 
     fn max_gas_price_gwei(&self) -> anyhow::Result<Option<U256>>;
 
     fn approve_gas_price_gwei(&self) -> anyhow::Result<Option<U256>>;
 
+    // [None] if the approve gas limit is either missing or 'auto'
     fn approve_gas_limit_u256(&self) -> anyhow::Result<Option<U256>>;
 
+    fn is_auto_approve_gas_limit_active(&self) -> bool;
+
+    fn max_approve_gas_limit_u256(&self) -> anyhow::Result<Option<U256>>;
+
     fn trade_at_price_u256(&self) -> anyhow::Result<Option<U256>>;
 
-    fn min_liquidity_required_u256(&self) -> anyhow::Result<Option<U256>>;
+    // [liquidity_token_decimals] is the 'decimals' of the token the liquidity is measured in
+    fn min_liquidity_required_u256(
+        &self,
+        liquidity_token_decimals: u8,
+    ) -> anyhow::Result<Option<U256>>;
+
+    fn path_h160(&self) -> anyhow::Result<Option<Vec<Address>>>;
+
+    fn is_auto_route_active(&self) -> bool;
+
+    fn is_abort_on_max_price_impact_active(&self) -> bool;
 }
 
 impl TradeSchemeVariant for BuyScheme {
-    fn gas_limit_u256(&self) -> anyhow::Result<U256> {
-        Ok(U256::from(self.gas_limit))
+    fn gas_limit_u256(&self) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::convert_to_u256_option(&self.gas_limit.fixed())
+    }
+
+    fn max_gas_limit_u256(&self) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::convert_to_u256_option(&self.max_gas_limit)
+    }
+
+    fn gas_limit_margin_perc(&self) -> u32 {
+        self.gas_limit_margin_perc
+            .unwrap_or(DefaultValues::GAS_LIMIT_MARGIN_PERC)
     }
 
-    fn gas_price_gwei(&self) -> anyhow::Result<U256> {
-        SchemeHelpers::decimals_price_to_gwei(&self.gas_price)
+    fn gas_price_gwei(&self) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::gas_price_to_gwei_option(&self.gas_price)
+    }
+
+    fn is_auto_gas_price_active(&self) -> bool {
+        SchemeHelpers::is_auto_keyword(&self.gas_price)
+    }
+
+    fn min_gas_price_gwei(&self) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::decimals_price_to_gwei_option(&self.min_gas_price)
     }
 
     fn max_gas_price_gwei(&self) -> anyhow::Result<Option<U256>> {
@@ -167,25 +280,70 @@ impl TradeSchemeVariant for BuyScheme {
     }
 
     fn approve_gas_limit_u256(&self) -> anyhow::Result<Option<U256>> {
-        SchemeHelpers::convert_to_u256_option(&self.approve_gas_limit)
+        let approve_gas_limit = self.approve_gas_limit.as_ref().and_then(|d| d.fixed());
+
+        SchemeHelpers::convert_to_u256_option(&approve_gas_limit)
+    }
+
+    fn is_auto_approve_gas_limit_active(&self) -> bool {
+        match &self.approve_gas_limit {
+            None => false,
+            Some(d) => d.is_auto(),
+        }
+    }
+
+    fn max_approve_gas_limit_u256(&self) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::convert_to_u256_option(&self.max_approve_gas_limit)
     }
 
     fn trade_at_price_u256(&self) -> anyhow::Result<Option<U256>> {
         SchemeHelpers::price_to_u256_option(&self.trade_at_price)
     }
 
-    fn min_liquidity_required_u256(&self) -> anyhow::Result<Option<U256>> {
-        SchemeHelpers::price_to_u256_option(&self.min_liquidity_required)
+    fn min_liquidity_required_u256(
+        &self,
+        liquidity_token_decimals: u8,
+    ) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::amount_to_u256_option(&self.min_liquidity_required, liquidity_token_decimals)
+    }
+
+    fn path_h160(&self) -> anyhow::Result<Option<Vec<Address>>> {
+        SchemeHelpers::contracts_to_h160_option(&self.path)
+    }
+
+    fn is_auto_route_active(&self) -> bool {
+        self.auto_route.unwrap_or(false)
+    }
+
+    fn is_abort_on_max_price_impact_active(&self) -> bool {
+        self.abort_on_max_price_impact.unwrap_or(false)
     }
 }
 
 impl TradeSchemeVariant for SellScheme {
-    fn gas_limit_u256(&self) -> anyhow::Result<U256> {
-        Ok(U256::from(self.gas_limit))
+    fn gas_limit_u256(&self) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::convert_to_u256_option(&self.gas_limit.fixed())
+    }
+
+    fn max_gas_limit_u256(&self) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::convert_to_u256_option(&self.max_gas_limit)
     }
 
-    fn gas_price_gwei(&self) -> anyhow::Result<U256> {
-        SchemeHelpers::decimals_price_to_gwei(&self.gas_price)
+    fn gas_limit_margin_perc(&self) -> u32 {
+        self.gas_limit_margin_perc
+            .unwrap_or(DefaultValues::GAS_LIMIT_MARGIN_PERC)
+    }
+
+    fn gas_price_gwei(&self) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::gas_price_to_gwei_option(&self.gas_price)
+    }
+
+    fn is_auto_gas_price_active(&self) -> bool {
+        SchemeHelpers::is_auto_keyword(&self.gas_price)
+    }
+
+    fn min_gas_price_gwei(&self) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::decimals_price_to_gwei_option(&self.min_gas_price)
     }
 
     fn max_gas_price_gwei(&self) -> anyhow::Result<Option<U256>> {
@@ -197,15 +355,43 @@ impl TradeSchemeVariant for SellScheme {
     }
 
     fn approve_gas_limit_u256(&self) -> anyhow::Result<Option<U256>> {
-        SchemeHelpers::convert_to_u256_option(&self.approve_gas_limit)
+        let approve_gas_limit = self.approve_gas_limit.as_ref().and_then(|d| d.fixed());
+
+        SchemeHelpers::convert_to_u256_option(&approve_gas_limit)
+    }
+
+    fn is_auto_approve_gas_limit_active(&self) -> bool {
+        match &self.approve_gas_limit {
+            None => false,
+            Some(d) => d.is_auto(),
+        }
+    }
+
+    fn max_approve_gas_limit_u256(&self) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::convert_to_u256_option(&self.max_approve_gas_limit)
     }
 
     fn trade_at_price_u256(&self) -> anyhow::Result<Option<U256>> {
         SchemeHelpers::price_to_u256_option(&self.trade_at_price)
     }
 
-    fn min_liquidity_required_u256(&self) -> anyhow::Result<Option<U256>> {
-        SchemeHelpers::price_to_u256_option(&self.min_liquidity_required)
+    fn min_liquidity_required_u256(
+        &self,
+        liquidity_token_decimals: u8,
+    ) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::amount_to_u256_option(&self.min_liquidity_required, liquidity_token_decimals)
+    }
+
+    fn path_h160(&self) -> anyhow::Result<Option<Vec<Address>>> {
+        SchemeHelpers::contracts_to_h160_option(&self.path)
+    }
+
+    fn is_auto_route_active(&self) -> bool {
+        self.auto_route.unwrap_or(false)
+    }
+
+    fn is_abort_on_max_price_impact_active(&self) -> bool {
+        self.abort_on_max_price_impact.unwrap_or(false)
     }
 }
 
@@ -218,8 +404,33 @@ impl BuyScheme {
         SchemeHelpers::contract_to_h160_option(&self.token_out_contract)
     }
 
-    pub fn amount_of_token_in_u256(&self) -> anyhow::Result<U256> {
-        SchemeHelpers::price_to_u256(&self.amount_of_token_in)
+    pub fn amount_of_token_in_u256(&self, token_in_decimals: u8) -> anyhow::Result<U256> {
+        SchemeHelpers::amount_to_u256(&self.amount_of_token_in, token_in_decimals)
+    }
+
+    pub fn amount_of_token_out_u256(&self, token_out_decimals: u8) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::amount_to_u256_option(&self.amount_of_token_out, token_out_decimals)
+    }
+
+    pub fn is_mempool_sniping_active(&self) -> bool {
+        self.snipe_liquidity_from_mempool.unwrap_or(false)
+    }
+
+    pub fn is_auto_tax_detection_active(&self) -> bool {
+        self.auto_detect_tax.unwrap_or(false)
+    }
+
+    pub fn is_honeypot_check_active(&self) -> bool {
+        self.honeypot_check.unwrap_or(false)
+    }
+
+    pub fn is_risk_scan_active(&self) -> bool {
+        self.risk_scan.unwrap_or(false)
+    }
+
+    pub fn honeypot_max_loss_perc(&self) -> u8 {
+        self.honeypot_max_loss_perc
+            .unwrap_or(DefaultValues::HONEYPOT_MAX_LOSS_PERC)
     }
 
     pub fn trade_actuator(&self) -> anyhow::Result<Option<TradeActuatorForBuy>> {
@@ -252,8 +463,25 @@ impl SellScheme {
         SchemeHelpers::contract_to_h160(&self.token_out_contract)
     }
 
-    pub fn amount_of_token_in_u256(&self) -> anyhow::Result<Option<U256>> {
-        SchemeHelpers::price_to_u256_option(&self.amount_of_token_in)
+    pub fn amount_of_token_in_u256(&self, token_in_decimals: u8) -> anyhow::Result<Option<U256>> {
+        SchemeHelpers::amount_to_u256_option(&self.amount_of_token_in, token_in_decimals)
+    }
+
+    pub fn is_auto_tax_detection_active(&self) -> bool {
+        self.auto_detect_tax.unwrap_or(false)
+    }
+
+    // whether any step of the take-profit ladder is triggered by a profit percentage
+    pub fn is_take_profit_ladder_perc_used(&self) -> bool {
+        match &self.take_profit_ladder {
+            None => false,
+            Some(l) => l.iter().any(|d| d.trade_at_profit_perc.is_some()),
+        }
+    }
+
+    pub fn rug_pull_perc_increase_gas_price(&self) -> u32 {
+        self.rug_pull_perc_increase_gas_price
+            .unwrap_or(DefaultValues::RUG_PULL_PERC_INCREASE_GAS_PRICE)
     }
 
     // we return either "static trade actuator" or "percentage trade actuator"
@@ -292,6 +520,57 @@ impl SellScheme {
             return Ok(Some(TradeActuatorForSell::Percentage(pt)));
         }
 
+        // trailing stop-loss trade actuator
+        if let Some(trailing_stop_loss_perc) = self.trailing_stop_loss_perc {
+            let tt = TrailingStopLossTradeActuatorForSell {
+                trailing_stop_loss_perc,
+                stop_loss_floor_price_u256: SchemeHelpers::price_to_u256_option(
+                    &self.stop_loss_floor_price,
+                )?,
+            };
+
+            return Ok(Some(TradeActuatorForSell::TrailingStopLoss(tt)));
+        }
+
+        Ok(None)
+    }
+}
+
+/// a single step of the take-profit ladder of the Sell function
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TakeProfitStep {
+    pub trade_at_price: Option<String>,
+
+    pub trade_at_profit_perc: Option<u8>,
+
+    pub perc_of_position: u8,
+}
+
+impl TakeProfitStep {
+    // a step is triggered by either a static price or a profit percentage
+    pub fn trade_actuator(&self) -> anyhow::Result<Option<TradeActuatorForSell>> {
+        let s = SchemeHelpers::static_trade_actuator(&self.trade_at_price, &None, &None)?;
+
+        if let Some(d) = s {
+            let st = StaticTradeActuatorForSell {
+                trade_at_price_u256: d.trade_at_price_u256,
+                hold_trade_below_price_u256: None,
+            };
+
+            return Ok(Some(TradeActuatorForSell::Static(st)));
+        };
+
+        let p = SchemeHelpers::percentage_trade_actuator(self.trade_at_profit_perc, None)?;
+
+        if let Some(d) = p {
+            let pt = PercentageTradeActuatorForSell {
+                trade_at_profit_perc: d.trade_at_profit_perc,
+                hold_trade_below_profit_perc: None,
+            };
+
+            return Ok(Some(TradeActuatorForSell::Percentage(pt)));
+        }
+
         Ok(None)
     }
 }
@@ -305,6 +584,7 @@ pub enum TradeActuatorForBuy {
 pub enum TradeActuatorForSell {
     Static(StaticTradeActuatorForSell),
     Percentage(PercentageTradeActuatorForSell),
+    TrailingStopLoss(TrailingStopLossTradeActuatorForSell),
 }
 
 #[derive(Debug)]
@@ -325,6 +605,12 @@ pub struct PercentageTradeActuatorForSell {
     pub hold_trade_below_profit_perc: Option<u8>,
 }
 
+#[derive(Debug)]
+pub struct TrailingStopLossTradeActuatorForSell {
+    pub trailing_stop_loss_perc: u8,
+    pub stop_loss_floor_price_u256: Option<U256>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Trades {
     pub trade: TradeScheme,
@@ -344,3 +630,76 @@ impl fmt::Display for TradeName {
         write!(f, "{:?}", self)
     }
 }
+
+/// [gas_limit] and [approve_gas_limit] are either a fixed number of gas units or the 'auto' keyword
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum GasLimit {
+    Fixed(u64),
+
+    Keyword(String),
+}
+
+impl GasLimit {
+    // [None] if the gas limit is a keyword
+    pub fn fixed(&self) -> Option<u64> {
+        match self {
+            GasLimit::Fixed(d) => Some(*d),
+            GasLimit::Keyword(_) => None,
+        }
+    }
+
+    pub fn is_auto(&self) -> bool {
+        match self {
+            GasLimit::Fixed(_) => false,
+            GasLimit::Keyword(d) => SchemeHelpers::is_auto_keyword(d),
+        }
+    }
+
+    // the highest gas limit the tx could be sent with
+    // the 'auto' gas limit is capped at [max_gas_limit]
+    pub fn highest(&self, max_gas_limit: Option<u64>) -> Option<u64> {
+        match self {
+            GasLimit::Fixed(d) => Some(*d),
+            GasLimit::Keyword(_) => max_gas_limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gas_limit(value: &str) -> GasLimit {
+        serde_yaml::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn gas_limit_number_deserializes_into_a_fixed_gas_limit() {
+        let g = gas_limit("300000");
+
+        assert_eq!(g.fixed(), Some(300000));
+        assert!(!g.is_auto());
+        assert_eq!(g.highest(Some(500000)), Some(300000));
+    }
+
+    #[test]
+    fn gas_limit_auto_deserializes_into_a_keyword() {
+        for value in &["auto", "AUTO"] {
+            let g = gas_limit(value);
+
+            assert_eq!(g.fixed(), None);
+            assert!(g.is_auto());
+            assert_eq!(g.highest(Some(500000)), Some(500000));
+            assert_eq!(g.highest(None), None);
+        }
+    }
+
+    #[test]
+    fn gas_limit_other_keyword_is_not_auto() {
+        let g = gas_limit("max");
+
+        assert_eq!(g.fixed(), None);
+        assert!(!g.is_auto());
+    }
+}
@@ -2,12 +2,21 @@ use crate::common::utils::f256::divide_into_f256;
 use crate::common::utils::u256::{decimals_to_u256, ToU256Units};
 use ethers::abi::ethereum_types::U256;
 use ethers::core::utils::{parse_units, WEI_IN_ETHER};
-use std::ops::Div;
+use std::ops::{Div, Mul};
 
 pub fn ether_to_human_display(value: U256) -> qd::Quad {
     divide_into_f256(&value, &WEI_IN_ETHER)
 }
 
+// [decimals] is the 'decimals' of the ERC20 token the [value] belongs to
+pub fn units_to_human_display(value: U256, decimals: u8) -> qd::Quad {
+    divide_into_f256(&value, &U256::exp10(decimals as usize))
+}
+
+pub fn gwei_to_human_display(value: U256) -> qd::Quad {
+    divide_into_f256(&value, &U256::exp10(9))
+}
+
 pub fn decimals_to_ethers<K>(value: &K) -> anyhow::Result<U256>
 where
     K: ToU256Units,
@@ -28,6 +37,26 @@ where
     Ok(value_corrected_u256)
 }
 
+// [decimals] is the 'decimals' of the ERC20 token the [value] belongs to
+pub fn decimals_to_units<K>(value: &K, decimals: u8) -> anyhow::Result<U256>
+where
+    K: ToU256Units,
+{
+    let (value_decimal_stripped_u256, value_decimals_unit) = decimals_to_u256(value)?;
+
+    let value_parsed_to_units = value_decimal_stripped_u256.mul(U256::exp10(decimals as usize));
+
+    // we first strip the decimal points out of it and then later divide the number by the 10^deciman_units
+    let value_corrected_u256: U256;
+    if value_decimals_unit > 0 {
+        value_corrected_u256 = value_parsed_to_units.div(U256::exp10(value_decimals_unit));
+    } else {
+        value_corrected_u256 = value_parsed_to_units;
+    }
+
+    Ok(value_corrected_u256)
+}
+
 pub fn decimals_to_gwei<K>(value: &K) -> anyhow::Result<U256>
 where
     K: ToU256Units,
@@ -1,6 +1,6 @@
 use ethers::core::abi::ethereum_types::U256;
 use qd::Quad;
-use std::ops::{Div, Mul};
+use std::ops::{Div, Mul, Sub};
 use std::str::FromStr;
 
 pub trait ToF256Units {
@@ -93,3 +93,16 @@ where
 
     (total_quad.mul(part_quad)).div(div_quad)
 }
+
+// percentage of change from [from] to [to]; a negative value denotes a decrease
+pub fn percentage_change_f256<K, Q>(from: &K, to: &Q) -> qd::Quad
+where
+    K: ToF256Units,
+    Q: ToF256Units,
+{
+    let from_quad = ToF256Units::to_f256(from);
+    let to_quad = ToF256Units::to_f256(to);
+    let mul_quad = ToF256Units::to_f256(&100_u8);
+
+    (to_quad.sub(from_quad)).div(from_quad).mul(mul_quad)
+}
@@ -1,3 +1,4 @@
+pub mod cancel_tx_inputs;
 pub mod cli_entry_points;
 pub mod entry_points;
 pub mod interactive_entry_points;
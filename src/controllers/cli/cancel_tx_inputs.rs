@@ -0,0 +1,48 @@
+use crate::common::utils::ether::decimals_to_gwei;
+use dialoguer::console::Term;
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Input, Select};
+use ethers::types::U256;
+
+pub struct CancelTxInputs;
+
+impl CancelTxInputs {
+    pub fn nonce(pending_nonces: &[U256]) -> anyhow::Result<U256> {
+        let selections: Vec<String> = pending_nonces
+            .iter()
+            .map(|d| format!("nonce: {}", d))
+            .collect();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select the pending tx to cancel")
+            .default(0)
+            .items(&selections)
+            .interact_on_opt(&Term::stderr())?;
+
+        let nonce = match selection.and_then(|index| pending_nonces.get(index)) {
+            Some(d) => *d,
+            None => paniq!("unknown interative cli input for the pending nonce (P00035)"),
+        };
+
+        Ok(nonce)
+    }
+
+    pub fn gas_price(default_gas_price: String) -> anyhow::Result<String> {
+        let gas_price: String = Input::new()
+            .with_prompt("Enter the gas price (GWEI) of the cancel tx, it should be at least 10% higher than the gas price of the pending tx")
+            .default(default_gas_price)
+            .validate_with(|input: &String| -> anyhow::Result<()> {
+                match decimals_to_gwei(input) {
+                    Ok(_) => Ok(()),
+                    Err(e) => {
+                        let fmt_e = format!("invalid gas price, try again. '{:?}'", e);
+                        log::error!("invalid gas price, try again. '{:?}'", e);
+
+                        Err(anyhow::Error::msg(fmt_e))
+                    }
+                }
+            })
+            .interact()?;
+
+        Ok(gas_price)
+    }
+}
@@ -37,6 +37,8 @@ impl EntryPoints {
                         TradeType::BuySell.to_string().to_lowercase().as_str(),
                         TradeType::Buy.to_string().to_lowercase().as_str(),
                         TradeType::Sell.to_string().to_lowercase().as_str(),
+                        TradeType::TokenTransfer.to_string().to_lowercase().as_str(),
+                        TradeType::CancelTx.to_string().to_lowercase().as_str(),
                     ]),
                 Arg::new("configcheck")
                     .about("Checks the config files")
@@ -8,6 +8,8 @@ pub enum TradeType {
     BuySell,
     Buy,
     Sell,
+    TokenTransfer,
+    CancelTx,
 }
 
 impl fmt::Display for TradeType {
@@ -24,6 +26,8 @@ impl FromStr for TradeType {
             "buysell" => Ok(TradeType::BuySell),
             "buy" => Ok(TradeType::Buy),
             "sell" => Ok(TradeType::Sell),
+            "tokentransfer" => Ok(TradeType::TokenTransfer),
+            "canceltx" => Ok(TradeType::CancelTx),
             _ => Err("an error occured while trying to convert string to 'TradeType'"),
         }
     }
@@ -41,6 +41,8 @@ impl EntryPoints {
             TradeType::BuySell.to_string(),
             TradeType::Buy.to_string(),
             TradeType::Sell.to_string(),
+            TradeType::TokenTransfer.to_string(),
+            TradeType::CancelTx.to_string(),
         ];
         let selection = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("Select your trade type")
@@ -58,6 +60,10 @@ impl EntryPoints {
                     t = TradeType::Buy;
                 } else if index == 2 {
                     t = TradeType::Sell;
+                } else if index == 3 {
+                    t = TradeType::TokenTransfer;
+                } else if index == 4 {
+                    t = TradeType::CancelTx;
                 } else {
                     paniq!("unknown interative cli input for trade type (P00010)")
                 }
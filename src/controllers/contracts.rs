@@ -1,4 +1,5 @@
 use ethers::abi::ethereum_types::Address;
+use ethers::contract::builders::ContractCall;
 use ethers::middleware::SignerMiddleware;
 use ethers::prelude::Provider;
 use ethers::prelude::{abigen, LocalWallet, Ws};
@@ -19,9 +20,46 @@ abigen!(
     RouterContract,
     r#"[
         function getAmountsOut(uint amountIn, address[] memory path) public view returns (uint[] memory amounts)
+        function getAmountsIn(uint amountOut, address[] memory path) public view returns (uint[] memory amounts)
         function swapExactTokensForTokens(uint amountIn, uint amountOutMin, address[] calldata path, address to, uint deadline) external returns (uint[] memory amounts)
         function swapExactTokensForTokensSupportingFeeOnTransferTokens(uint amountIn, uint amountOutMin, address[] calldata path, address to, uint deadline) external returns (uint[] memory amounts)
+        function swapExactETHForTokens(uint amountOutMin, address[] calldata path, address to, uint deadline) external payable returns (uint[] memory amounts)
+        function swapExactETHForTokensSupportingFeeOnTransferTokens(uint amountOutMin, address[] calldata path, address to, uint deadline) external payable
+        function swapExactTokensForETH(uint amountIn, uint amountOutMin, address[] calldata path, address to, uint deadline) external returns (uint[] memory amounts)
+        function swapExactTokensForETHSupportingFeeOnTransferTokens(uint amountIn, uint amountOutMin, address[] calldata path, address to, uint deadline) external
+        function swapTokensForExactTokens(uint amountOut, uint amountInMax, address[] calldata path, address to, uint deadline) external returns (uint[] memory amounts)
+        function swapETHForExactTokens(uint amountOut, address[] calldata path, address to, uint deadline) external payable returns (uint[] memory amounts)
+        function swapTokensForExactETH(uint amountOut, uint amountInMax, address[] calldata path, address to, uint deadline) external returns (uint[] memory amounts)
         function approve(address _spender, uint256 value) external returns(bool)
+        function addLiquidity(address tokenA, address tokenB, uint amountADesired, uint amountBDesired, uint amountAMin, uint amountBMin, address to, uint deadline) external returns (uint amountA, uint amountB, uint liquidity)
+        function addLiquidityETH(address token, uint amountTokenDesired, uint amountTokenMin, uint amountETHMin, address to, uint deadline) external payable returns (uint amountToken, uint amountETH, uint liquidity)
+        function removeLiquidity(address tokenA, address tokenB, uint liquidity, uint amountAMin, uint amountBMin, address to, uint deadline) external returns (uint amountA, uint amountB)
+        function removeLiquidityETH(address token, uint liquidity, uint amountTokenMin, uint amountETHMin, address to, uint deadline) external returns (uint amountToken, uint amountETH)
+        function removeLiquidityWithPermit(address tokenA, address tokenB, uint liquidity, uint amountAMin, uint amountBMin, address to, uint deadline, bool approveMax, uint8 v, bytes32 r, bytes32 s) external returns (uint amountA, uint amountB)
+        function removeLiquidityETHWithPermit(address token, uint liquidity, uint amountTokenMin, uint amountETHMin, address to, uint deadline, bool approveMax, uint8 v, bytes32 r, bytes32 s) external returns (uint amountToken, uint amountETH)
+        function removeLiquidityETHSupportingFeeOnTransferTokens(address token, uint liquidity, uint amountTokenMin, uint amountETHMin, address to, uint deadline) external returns (uint amountETH)
+        function removeLiquidityETHWithPermitSupportingFeeOnTransferTokens(address token, uint liquidity, uint amountTokenMin, uint amountETHMin, address to, uint deadline, bool approveMax, uint8 v, bytes32 r, bytes32 s) external returns (uint amountETH)
+    ]"#,
+    event_derives(serde::Deserialize, serde::Serialize)
+);
+
+abigen!(
+    PairContract,
+    r#"[
+        function token0() external view returns (address)
+        function token1() external view returns (address)
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast)
+        function totalSupply() external view returns (uint)
+        function transfer(address to, uint value) external returns (bool)
+        function transferFrom(address from, address to, uint value) external returns (bool)
+    ]"#,
+    event_derives(serde::Deserialize, serde::Serialize)
+);
+
+abigen!(
+    OwnableContract,
+    r#"[
+        function owner() external view returns (address)
     ]"#,
     event_derives(serde::Deserialize, serde::Serialize)
 );
@@ -41,12 +79,21 @@ pub type Erc20Contract =
 pub type RouterContract =
     routercontract_mod::RouterContract<SignerMiddleware<Provider<Ws>, LocalWallet>>;
 
+pub type PairContract = paircontract_mod::PairContract<SignerMiddleware<Provider<Ws>, LocalWallet>>;
+
+pub type OwnableContract =
+    ownablecontract_mod::OwnableContract<SignerMiddleware<Provider<Ws>, LocalWallet>>;
+
+pub type Erc20TransferFilter = erc20contract_mod::TransferFilter;
+
 #[derive(Debug)]
 pub struct QuantContracts {
     pub factory: FactoryContract,
     pub token_in_erc20: Erc20Contract,
     pub native_token_erc20: Erc20Contract,
     pub router: RouterContract,
+    pub token_in_decimals: u8,
+    pub token_out_decimals: u8,
 }
 
 #[derive(Debug)]
@@ -54,6 +101,7 @@ pub struct QuantContractsArgs<'a> {
     pub client: &'a Arc<SignerMiddleware<Provider<Ws>, LocalWallet>>,
     pub factory_addr_h160: Address,
     pub token_in_h160: Address,
+    pub token_out_h160: Address,
     pub router_in_h160: Address,
     pub native_token_h160: Address,
 }
@@ -66,13 +114,27 @@ impl QuantContracts {
         FactoryContract::new(factory_addr, client.clone())
     }
 
-    fn ecr20_contract(
+    pub fn ecr20_contract(
         client: &Arc<SignerMiddleware<Provider<Ws>, LocalWallet>>,
         erc20_token_h160: Address,
     ) -> Erc20Contract {
         Erc20Contract::new(erc20_token_h160, client.clone())
     }
 
+    pub fn pair_contract(
+        client: &Arc<SignerMiddleware<Provider<Ws>, LocalWallet>>,
+        pair_h160: Address,
+    ) -> PairContract {
+        PairContract::new(pair_h160, client.clone())
+    }
+
+    pub fn ownable_contract(
+        client: &Arc<SignerMiddleware<Provider<Ws>, LocalWallet>>,
+        token_h160: Address,
+    ) -> OwnableContract {
+        OwnableContract::new(token_h160, client.clone())
+    }
+
     fn router_contract(
         client: &Arc<SignerMiddleware<Provider<Ws>, LocalWallet>>,
         router_in: Address,
@@ -80,12 +142,31 @@ impl QuantContracts {
         RouterContract::new(router_in, client.clone())
     }
 
-    pub fn new(args: QuantContractsArgs) -> QuantContracts {
+    // fetch the 'decimals' of an ERC20 token
+    pub async fn erc20_decimals(erc20: &Erc20Contract) -> anyhow::Result<u8> {
+        let erc20_decimals_contract_call: ContractCall<
+            SignerMiddleware<Provider<Ws>, LocalWallet>,
+            u8,
+        > = erc20.decimals();
+
+        let decimals = erc20_decimals_contract_call.call().await?;
+
+        log::debug!(
+            "decimals of the token ({:?}): {}",
+            erc20.address(),
+            decimals
+        );
+
+        Ok(decimals)
+    }
+
+    pub async fn new(args: QuantContractsArgs) -> anyhow::Result<QuantContracts> {
         log::debug!("initializing contracts...");
 
         let client = args.client;
         let factory_addr_h160 = args.factory_addr_h160;
         let token_in_h160 = args.token_in_h160;
+        let token_out_h160 = args.token_out_h160;
         let native_token_h160 = args.native_token_h160;
         let router_in_h160 = args.router_in_h160;
 
@@ -94,11 +175,20 @@ impl QuantContracts {
         let native_token_erc20 = QuantContracts::ecr20_contract(client, native_token_h160);
         let router = QuantContracts::router_contract(client, router_in_h160);
 
-        QuantContracts {
+        // the amounts of [token_in] and [token_out] are converted using their own decimals
+        let token_in_decimals = QuantContracts::erc20_decimals(&token_in_erc20).await?;
+        let token_out_erc20 = QuantContracts::ecr20_contract(client, token_out_h160);
+        let token_out_decimals = QuantContracts::erc20_decimals(&token_out_erc20).await?;
+
+        let c = QuantContracts {
             factory,
             token_in_erc20,
             native_token_erc20,
             router,
-        }
+            token_in_decimals,
+            token_out_decimals,
+        };
+
+        Ok(c)
     }
 }
@@ -1,13 +1,19 @@
 use ethers::middleware::SignerMiddleware;
-use ethers::prelude::{Ws, Signer};
+use ethers::prelude::{Middleware, Signer, Ws};
 use ethers::providers::Provider;
 use ethers::signers::LocalWallet;
+use ethers::types::{BlockNumber, U256};
 use std::sync::Arc;
+use tokio::sync::Mutex;
 
 #[derive(Debug)]
 pub struct QuantMiddleware {
     pub wallet: LocalWallet,
     pub client: Arc<SignerMiddleware<Provider<Ws>, LocalWallet>>,
+
+    // the nonce of the next tx of the account
+    // it is fetched from the network on the first use and after a reset
+    nonce: Mutex<Option<U256>>,
 }
 
 impl QuantMiddleware {
@@ -36,6 +42,89 @@ impl QuantMiddleware {
         let wallet = QuantMiddleware::wallet(chain_id, mnemonic)?;
         let client = QuantMiddleware::client(provider, &wallet)?;
 
-        Ok(QuantMiddleware { wallet, client })
+        Ok(QuantMiddleware {
+            wallet,
+            client,
+            nonce: Mutex::new(None),
+        })
+    }
+
+    // hand out the nonce of a new tx of the account
+    // the trades are carried out at the same time, so the nonces are handed out one at a time
+    pub async fn next_nonce(&self) -> anyhow::Result<U256> {
+        let mut nonce = self.nonce.lock().await;
+
+        let next_nonce = match *nonce {
+            Some(d) => d,
+            None => {
+                self.client
+                    .get_transaction_count(self.wallet.address(), Some(BlockNumber::Pending.into()))
+                    .await?
+            }
+        };
+
+        *nonce = Some(next_nonce + 1);
+
+        log::debug!("handing out the nonce: {}", next_nonce);
+
+        Ok(next_nonce)
+    }
+
+    // the nonces of the txs of the account that are sent but not mined yet
+    pub async fn pending_nonces(&self) -> anyhow::Result<Vec<U256>> {
+        let address = self.wallet.address();
+
+        let latest_nonce = self
+            .client
+            .get_transaction_count(address, Some(BlockNumber::Latest.into()))
+            .await?;
+        let pending_nonce = self
+            .client
+            .get_transaction_count(address, Some(BlockNumber::Pending.into()))
+            .await?;
+
+        Ok(Self::nonces_between(latest_nonce, pending_nonce))
+    }
+
+    // the nonces from [latest_nonce] up to, but excluding, [pending_nonce]
+    fn nonces_between(latest_nonce: U256, pending_nonce: U256) -> Vec<U256> {
+        let mut nonces: Vec<U256> = vec![];
+        let mut nonce = latest_nonce;
+
+        while nonce < pending_nonce {
+            nonces.push(nonce);
+            nonce += U256::one();
+        }
+
+        nonces
+    }
+
+    // the nonce is fetched again from the network on the next use
+    // a tx that failed to be sent leaves a gap in the nonces, which is filled by the next tx
+    pub async fn reset_nonce(&self) {
+        log::debug!("resetting the nonce...");
+
+        *self.nonce.lock().await = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nonces_between_the_latest_and_the_pending_nonce() {
+        assert_eq!(
+            QuantMiddleware::nonces_between(U256::from(5), U256::from(8)),
+            vec![U256::from(5), U256::from(6), U256::from(7)]
+        );
+    }
+
+    #[test]
+    fn nonces_between_without_a_pending_tx() {
+        assert!(QuantMiddleware::nonces_between(U256::from(5), U256::from(5)).is_empty());
+
+        // the pending nonce can lag behind the latest nonce on some nodes
+        assert!(QuantMiddleware::nonces_between(U256::from(5), U256::from(4)).is_empty());
     }
 }
@@ -1,26 +1,31 @@
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
 use ethers::abi::ethereum_types::Address;
 
 use crate::common::constants::chain_ids::ChainIds;
 use crate::common::errors::SetupError;
 use crate::common::helpers::parsers::setting_files::SettingFiles;
-use crate::common::models::config::{NetworkEntity, NetworkName};
-use crate::common::models::secrets::AccountEntity;
+use crate::common::helpers::timer::StdinInterrupter;
+use crate::common::models::config::{Budget, NetworkEntity, NetworkName};
+use crate::common::models::secrets::{AccountEntity, Accounts};
+use crate::common::models::token_transfer_scheme::{TokenTransfer, TokenTransfers};
 use crate::common::models::trade_scheme::{
     BuyScheme, SellScheme, TradeScheme, TradeSchemeVariant, Trades,
 };
 use crate::common::models::{config, secrets};
 use crate::controllers::cli::entry_points::{EntryPoints, TradeType};
 use crate::controllers::cli::trade_inputs::TradeInputs;
-use crate::controllers::contracts::{QuantContracts, QuantContractsArgs};
+use crate::controllers::contracts::{Erc20Contract, QuantContracts, QuantContractsArgs};
 use crate::controllers::middleware::QuantMiddleware;
 use crate::controllers::providers::QuantProvider;
-use crate::controllers::quant::trade_validation::{CheckGasFeesBalanceVariables, TradeValidation};
+use crate::controllers::quant::trade_validation::{
+    CheckBudgetVariables, CheckGasFeesBalanceVariables, TradeValidation,
+};
 
 type TradingContexts = (
-    Option<Vec<TradeContext<BuyScheme>>>,
-    Option<Vec<TradeContext<SellScheme>>>,
+    Option<Vec<Arc<TradeContext<BuyScheme>>>>,
+    Option<Vec<Arc<TradeContext<SellScheme>>>>,
 );
 
 #[derive(Debug)]
@@ -29,21 +34,42 @@ pub struct Quant {
     pub middleware: QuantMiddleware,
     pub provider: QuantProvider,
     pub feature: QuantFeature,
+
+    /// guards the writes to the ledger file of the network
+    pub ledger_lock: Mutex<()>,
+
+    /// the interrupter keywords typed in are shared by all the trades
+    pub interrupter: StdinInterrupter,
 }
 
 #[derive(Debug)]
 pub enum QuantFeature {
     TokenTransfers(QuantTokenTransfer),
     Trading(QuantTrading),
+
+    /// cancels a pending tx of the account, it doesn't need any entity in the config file
+    CancelTx,
 }
 
 #[derive(Debug)]
-pub struct QuantTokenTransfer {}
+pub struct QuantTokenTransfer {
+    pub transfer_context: Vec<TransferContext>,
+}
 
+#[derive(Debug)]
+pub struct TransferContext {
+    pub token_erc20: Erc20Contract,
+    pub token_decimals: u8,
+    pub scheme: TokenTransfer,
+    pub recipient_wallet_address_h160: Address,
+}
+
+/// every trade entity is carried out as its own task.
+/// in a BuySell trade the Buy and the Sell of the same position in both the buckets are paired
 #[derive(Debug)]
 pub struct QuantTrading {
-    pub buy_context: Option<Vec<TradeContext<BuyScheme>>>,
-    pub sell_context: Option<Vec<TradeContext<SellScheme>>>,
+    pub buy_context: Option<Vec<Arc<TradeContext<BuyScheme>>>>,
+    pub sell_context: Option<Vec<Arc<TradeContext<SellScheme>>>>,
 }
 
 #[derive(Debug)]
@@ -73,6 +99,10 @@ where
     /// these fields requires user's input via CLI
     /// use [feed] field when you need to access those fields
     pub feed: TradeCliParsed,
+
+    /// candidate swap paths from [token_in] to [token_out].
+    /// there is a single path unless [auto_route] is active
+    pub routes: Vec<Vec<Address>>,
 }
 
 #[derive(Debug)]
@@ -144,24 +174,56 @@ impl<'a> Quant {
         Ok(m)
     }
 
-    fn contracts(args: QuantContractsArgs) -> anyhow::Result<QuantContracts> {
-        let c = QuantContracts::new(args);
+    async fn contracts(args: QuantContractsArgs) -> anyhow::Result<QuantContracts> {
+        let c = QuantContracts::new(args).await?;
 
         Ok(c)
     }
 
+    // swap paths from [token_in] to [token_out]
+    // [path] holds the intermediary tokens of a fixed route
+    // [auto_route] tries the direct route, the route via [native_token_contract] and the route via [usd_token_contract]
+    fn routes<T>(
+        s: &T,
+        cli_parsed: &TradeCliParsed,
+        variables: &QuantVariables,
+    ) -> anyhow::Result<Vec<Vec<Address>>>
+    where
+        T: TradeSchemeVariant,
+    {
+        let token_in_h160 = cli_parsed.token_in_h160;
+        let token_out_h160 = cli_parsed.token_out_h160;
+
+        if let Some(path) = s.path_h160()? {
+            let mut route = vec![token_in_h160];
+            route.extend(path);
+            route.push(token_out_h160);
+
+            return Ok(vec![route]);
+        }
+
+        let mut routes = vec![vec![token_in_h160, token_out_h160]];
+
+        if s.is_auto_route_active() {
+            for via_h160 in &[variables.native_token_h160, variables.usd_token_h160] {
+                if *via_h160 == token_in_h160 || *via_h160 == token_out_h160 {
+                    continue;
+                }
+
+                routes.push(vec![token_in_h160, *via_h160, token_out_h160]);
+            }
+        }
+
+        Ok(routes)
+    }
+
     async fn set_buy_queue(
-        ctx: &Option<Vec<TradeContext<BuyScheme>>>,
         s: BuyScheme,
         variables: &QuantVariables,
         middleware: &QuantMiddleware,
     ) -> anyhow::Result<TradeContext<BuyScheme>> {
         log::debug!("setting up the Buy function");
 
-        if ctx.is_some() {
-            paniq!("config file error: a maximum of one trade entity for 'buy' in 'config.trades.trade' is allowed (P00010a)");
-        }
-
         TradeValidation::sanity_check_trade_schemes(&TradeScheme::Buy(s.clone())).await?;
 
         let token_out_contract: String;
@@ -199,23 +261,25 @@ impl<'a> Quant {
             client: &middleware.client,
             factory_addr_h160: variables.factory_addr_h160,
             token_in_h160: cli_parsed.token_in_h160,
+            token_out_h160: cli_parsed.token_out_h160,
             router_in_h160: variables.router_in_h160,
             native_token_h160: variables.native_token_h160,
         };
 
-        let q_contracts = Self::contracts(q_args)?;
+        let q_contracts = Self::contracts(q_args).await?;
+        let routes = Self::routes(&s, &cli_parsed, variables)?;
 
         let c = TradeContext {
             contracts: q_contracts,
             scheme: s,
             feed: cli_parsed,
+            routes,
         };
 
         Ok(c)
     }
 
     async fn set_sell_queue(
-        ctx: &Option<Vec<TradeContext<SellScheme>>>,
         s: SellScheme,
         variables: &QuantVariables,
         middleware: &QuantMiddleware,
@@ -223,10 +287,6 @@ impl<'a> Quant {
     ) -> anyhow::Result<TradeContext<SellScheme>> {
         log::debug!("setting up the Sell function");
 
-        if ctx.is_some() {
-            paniq!("config file error: a maximum of one trade entity for 'sell' in 'config.trades.trade' is allowed (P00010b)");
-        }
-
         TradeValidation::sanity_check_trade_schemes(&TradeScheme::Sell(s.clone())).await?;
 
         let token_in_contract: String;
@@ -275,32 +335,144 @@ impl<'a> Quant {
             client: &middleware.client,
             factory_addr_h160: variables.factory_addr_h160,
             token_in_h160: cli_parsed.token_in_h160,
+            token_out_h160: cli_parsed.token_out_h160,
             router_in_h160: variables.router_in_h160,
             native_token_h160: variables.native_token_h160,
         };
 
-        let q_contracts = Self::contracts(q_args)?;
+        let q_contracts = Self::contracts(q_args).await?;
+        let routes = Self::routes(&s, &cli_parsed, variables)?;
 
         let c = TradeContext {
             contracts: q_contracts,
             scheme: s,
             feed: cli_parsed,
+            routes,
         };
 
         Ok(c)
     }
 
+    async fn transfer_contexts(
+        token_transfers: &[TokenTransfers],
+        accounts: &[Accounts],
+        middleware: &QuantMiddleware,
+    ) -> anyhow::Result<Vec<TransferContext>> {
+        let mut transfer_context: Vec<TransferContext> = vec![];
+
+        for t in token_transfers {
+            log::debug!("setting up the Token Transfer function");
+
+            let s = t.token_transfer.clone();
+
+            TradeValidation::sanity_check_token_transfer_scheme(&s, accounts)?;
+
+            let token_erc20 = QuantContracts::ecr20_contract(&middleware.client, s.token_h160()?);
+            let token_decimals = QuantContracts::erc20_decimals(&token_erc20).await?;
+            let recipient_wallet_address_h160 = s.recipient_wallet_address_h160()?;
+
+            let c = TransferContext {
+                token_erc20,
+                token_decimals,
+                scheme: s,
+                recipient_wallet_address_h160,
+            };
+
+            transfer_context.push(c);
+        }
+
+        Ok(transfer_context)
+    }
+
+    // the native coin spent by the Buy function has to be available in the account along with the gas fees
+    // an exact output Buy spends at most [amount_of_token_in] as well
+    fn native_amount_of_token_in(
+        ctx: &TradeContext<BuyScheme>,
+        variables: &QuantVariables,
+    ) -> Option<String> {
+        if ctx.feed.token_in_h160 != variables.native_token_h160 {
+            return None;
+        }
+
+        Some(ctx.scheme.amount_of_token_in.to_owned())
+    }
+
+    // the gas fees variables of a single trade entry
+    // both [buy_ctx] and [sell_ctx] are available only in a BuySell trade
+    fn gas_fees_variables(
+        buy_ctx: Option<&TradeContext<BuyScheme>>,
+        sell_ctx: Option<&TradeContext<SellScheme>>,
+        variables: &QuantVariables,
+    ) -> CheckGasFeesBalanceVariables {
+        CheckGasFeesBalanceVariables {
+            account_address_h160: variables.account_address_h160,
+            buy_approve_gas_price: buy_ctx.and_then(|d| d.scheme.approve_gas_price.to_owned()),
+            buy_approve_gas_limit: buy_ctx.and_then(|d| {
+                d.scheme
+                    .approve_gas_limit
+                    .as_ref()?
+                    .highest(d.scheme.max_approve_gas_limit)
+            }),
+            buy_gas_price: buy_ctx.map(|d| d.scheme.gas_price.to_owned()),
+            buy_gas_limit: buy_ctx.and_then(|d| d.scheme.gas_limit.highest(d.scheme.max_gas_limit)),
+            buy_retry_attempts: buy_ctx.and_then(|d| d.scheme.retry_attempts),
+            buy_perc_increase_gas_price: buy_ctx.and_then(|d| d.scheme.perc_increase_gas_price),
+            buy_gas_price_multiplier_perc: buy_ctx.and_then(|d| d.scheme.gas_price_multiplier_perc),
+            buy_min_gas_price: buy_ctx.and_then(|d| d.scheme.min_gas_price.to_owned()),
+            buy_max_gas_price: buy_ctx.and_then(|d| d.scheme.max_gas_price.to_owned()),
+            buy_native_amount_of_token_in: buy_ctx
+                .and_then(|d| Self::native_amount_of_token_in(d, variables)),
+
+            sell_approve_gas_price: sell_ctx.and_then(|d| d.scheme.approve_gas_price.to_owned()),
+            sell_approve_gas_limit: sell_ctx.and_then(|d| {
+                d.scheme
+                    .approve_gas_limit
+                    .as_ref()?
+                    .highest(d.scheme.max_approve_gas_limit)
+            }),
+            sell_gas_price: sell_ctx.map(|d| d.scheme.gas_price.to_owned()),
+            sell_gas_limit: sell_ctx
+                .and_then(|d| d.scheme.gas_limit.highest(d.scheme.max_gas_limit)),
+            sell_retry_attempts: sell_ctx.and_then(|d| d.scheme.retry_attempts),
+            sell_perc_increase_gas_price: sell_ctx.and_then(|d| d.scheme.perc_increase_gas_price),
+            sell_gas_price_multiplier_perc: sell_ctx
+                .and_then(|d| d.scheme.gas_price_multiplier_perc),
+            sell_min_gas_price: sell_ctx.and_then(|d| d.scheme.min_gas_price.to_owned()),
+            sell_max_gas_price: sell_ctx.and_then(|d| d.scheme.max_gas_price.to_owned()),
+
+            native_token_symbol: variables.native_token_symbol.to_owned(),
+        }
+    }
+
+    // the [token_in] planned to be spent by every Buy and Sell function of the session
+    fn budget_variables(
+        buy_bucket: &[Arc<TradeContext<BuyScheme>>],
+        sell_bucket: &[Arc<TradeContext<SellScheme>>],
+    ) -> Vec<CheckBudgetVariables> {
+        let buy_budget_variables = buy_bucket.iter().map(|d| CheckBudgetVariables {
+            token_in_h160: d.feed.token_in_h160,
+            token_in_decimals: d.contracts.token_in_decimals,
+            amount_of_token_in: Some(d.scheme.amount_of_token_in.to_owned()),
+        });
+
+        let sell_budget_variables = sell_bucket.iter().map(|d| CheckBudgetVariables {
+            token_in_h160: d.feed.token_in_h160,
+            token_in_decimals: d.contracts.token_in_decimals,
+            amount_of_token_in: d.scheme.amount_of_token_in.to_owned(),
+        });
+
+        buy_budget_variables.chain(sell_budget_variables).collect()
+    }
+
     async fn trading_contexts(
         entry_points: &'a EntryPoints,
         trades: &[Trades],
+        budget: Option<&Budget>,
         variables: &QuantVariables,
         middleware: &QuantMiddleware,
     ) -> anyhow::Result<TradingContexts> {
-        let mut buy_context: Option<Vec<TradeContext<BuyScheme>>> = None;
-        let mut sell_context: Option<Vec<TradeContext<SellScheme>>> = None;
-
-        let mut sell_token_in_contract: Option<String> = None;
-        let mut buy_token_out_contract: Option<String> = None;
+        let mut buy_bucket: Vec<Arc<TradeContext<BuyScheme>>> = vec![];
+        let mut sell_bucket: Vec<Arc<TradeContext<SellScheme>>> = vec![];
 
         match entry_points.trade_type {
             TradeType::BuySell => {
@@ -312,12 +484,9 @@ impl<'a> Quant {
                                 &entry_points.trade_type,
                             )?;
 
-                            let o =
-                                Self::set_buy_queue(&buy_context, s, variables, middleware).await?;
-
-                            buy_token_out_contract = Some(o.feed.token_out_contract.to_owned());
+                            let o = Self::set_buy_queue(s, variables, middleware).await?;
 
-                            buy_context = Some(vec![o]);
+                            buy_bucket.push(Arc::new(o));
                         }
                         TradeScheme::Sell(s) => {
                             TradeValidation::sanity_check_trade_schemes_for_trade_types(
@@ -325,33 +494,14 @@ impl<'a> Quant {
                                 &entry_points.trade_type,
                             )?;
 
-                            let cli_parsed: Option<&TradeCliParsed>;
-
-                            // todo: by default we are selecting the first item in the [buy_context_bucket] to check whether a [TradeCliParsed] entity already exists or not which needs to be parsed into the Buy function
-                            cli_parsed = match &buy_context {
-                                None => None,
-                                Some(v) => {
-                                    let buy_ctx_first = v.first();
-
-                                    match buy_ctx_first {
-                                        None => None,
-                                        Some(d) => Some(&d.feed),
-                                    }
-                                }
-                            };
-
-                            let o = Self::set_sell_queue(
-                                &sell_context,
-                                s,
-                                variables,
-                                middleware,
-                                cli_parsed,
-                            )
-                            .await?;
-
-                            sell_token_in_contract = Some(o.feed.token_in_contract.to_owned());
-
-                            sell_context = Some(vec![o]);
+                            // the Sell is paired with the Buy of the same position in 'config.trades.trade'
+                            // the [TradeCliParsed] entity of the paired Buy is parsed into the Sell function
+                            let cli_parsed = buy_bucket.get(sell_bucket.len()).map(|d| &d.feed);
+
+                            let o =
+                                Self::set_sell_queue(s, variables, middleware, cli_parsed).await?;
+
+                            sell_bucket.push(Arc::new(o));
                         }
                     };
                 }
@@ -364,9 +514,9 @@ impl<'a> Quant {
                             &entry_points.trade_type,
                         )?;
 
-                        let o = Self::set_buy_queue(&buy_context, s, variables, middleware).await?;
+                        let o = Self::set_buy_queue(s, variables, middleware).await?;
 
-                        buy_context = Some(vec![o]);
+                        buy_bucket.push(Arc::new(o));
                     };
                 }
             }
@@ -378,186 +528,172 @@ impl<'a> Quant {
                             &entry_points.trade_type,
                         )?;
 
-                        let o = Self::set_sell_queue(&sell_context, s, variables, middleware, None)
-                            .await?;
+                        let o = Self::set_sell_queue(s, variables, middleware, None).await?;
 
-                        sell_context = Some(vec![o]);
+                        sell_bucket.push(Arc::new(o));
                     };
                 }
             }
+            TradeType::TokenTransfer => {
+                return Err(SetupError::Settings(
+                    "token transfers cannot be used as a trade entity (E00009a)",
+                )
+                .into());
+            }
+            TradeType::CancelTx => {
+                return Err(SetupError::Settings(
+                    "cancelling a tx cannot be used as a trade entity (E00009b)",
+                )
+                .into());
+            }
         }
 
+        let mut gas_fees_variables: Vec<CheckGasFeesBalanceVariables> = vec![];
+
         match entry_points.trade_type {
             TradeType::BuySell => {
-                let mut buy_approve_gas_price: Option<String> = None;
-                let mut buy_approve_gas_limit: Option<u64> = None;
-                let mut buy_gas_price: Option<String> = None;
-                let mut buy_gas_limit: Option<u64> = None;
-                let mut buy_retry_attempts: Option<u64> = None;
-                let mut buy_perc_increase_gas_price: Option<u32> = None;
-
-                let mut sell_approve_gas_price: Option<String> = None;
-                let mut sell_approve_gas_limit: Option<u64> = None;
-                let mut sell_gas_price: Option<String> = None;
-                let mut sell_gas_limit: Option<u64> = None;
-                let mut sell_retry_attempts: Option<u64> = None;
-                let mut sell_perc_increase_gas_price: Option<u32> = None;
-
-                match &buy_context {
-                    None => {
-                        return Err(SetupError::Settings(
-                            "no trade entity found for 'buy' (E00006aa)",
-                        )
-                        .into());
-                    }
-                    Some(d) => {
-                        // todo: we are only picking up the first item from here. we need to improve the validation here
-                        if let Some(v) = d.first() {
-                            buy_approve_gas_price = v.scheme.approve_gas_price.to_owned();
-                            buy_gas_price = Some(v.scheme.gas_price.to_owned());
-                            buy_approve_gas_limit = v.scheme.approve_gas_limit;
-                            buy_gas_limit = Some(v.scheme.gas_limit);
-                            buy_retry_attempts = v.scheme.retry_attempts;
-                            buy_perc_increase_gas_price = v.scheme.perc_increase_gas_price;
-                        }
-                    }
+                if buy_bucket.is_empty() {
+                    return Err(
+                        SetupError::Settings("no trade entity found for 'buy' (E00006aa)").into(),
+                    );
                 }
 
-                match &sell_context {
-                    None => {
-                        return Err(SetupError::Settings(
-                            "no trade entity found for 'sell' (E00006ab)",
-                        )
-                        .into());
-                    }
-                    Some(d) => {
-                        // todo: we are only picking up the first item from here. we need to improve the validation here
-                        if let Some(v) = d.first() {
-                            sell_approve_gas_price = v.scheme.approve_gas_price.to_owned();
-                            sell_gas_price = Some(v.scheme.gas_price.to_owned());
-                            sell_approve_gas_limit = v.scheme.approve_gas_limit;
-                            sell_gas_limit = Some(v.scheme.gas_limit);
-                            sell_retry_attempts = v.scheme.retry_attempts;
-                            sell_perc_increase_gas_price = v.scheme.perc_increase_gas_price;
-                        }
-                    }
+                if sell_bucket.is_empty() {
+                    return Err(SetupError::Settings(
+                        "no trade entity found for 'sell' (E00006ab)",
+                    )
+                    .into());
                 }
 
-                TradeValidation::validate_buysell_fn_tokens(
-                    buy_token_out_contract,
-                    sell_token_in_contract,
-                )?;
-
-                if variables.check_gas_fees {
-                    let v = CheckGasFeesBalanceVariables {
-                        account_address_h160: variables.account_address_h160,
-                        buy_approve_gas_price,
-                        buy_approve_gas_limit,
-                        buy_gas_price,
-                        buy_gas_limit,
-                        buy_retry_attempts,
-                        buy_perc_increase_gas_price,
-                        sell_approve_gas_price,
-                        sell_gas_price,
-                        sell_gas_limit,
-                        sell_approve_gas_limit,
-                        sell_retry_attempts,
-                        sell_perc_increase_gas_price,
-                        native_token_symbol: variables.native_token_symbol.to_owned(),
-                    };
+                if buy_bucket.len() != sell_bucket.len() {
+                    paniq!("config file error: every trade entity for 'buy' requires a matching trade entity for 'sell' in 'config.trades.trade' (P00010c)");
+                }
 
-                    TradeValidation::check_gas_fees_balance(middleware, &v).await?;
+                for (buy_ctx, sell_ctx) in buy_bucket.iter().zip(sell_bucket.iter()) {
+                    TradeValidation::validate_buysell_fn_tokens(
+                        Some(buy_ctx.feed.token_out_contract.to_owned()),
+                        Some(sell_ctx.feed.token_in_contract.to_owned()),
+                    )?;
+
+                    TradeValidation::validate_buysell_profit_perc_tokens(
+                        &sell_ctx.scheme,
+                        Some(buy_ctx.feed.token_in_contract.to_owned()),
+                        Some(sell_ctx.feed.token_out_contract.to_owned()),
+                    )?;
+
+                    gas_fees_variables.push(Self::gas_fees_variables(
+                        Some(buy_ctx.as_ref()),
+                        Some(sell_ctx.as_ref()),
+                        variables,
+                    ));
                 }
             }
             TradeType::Buy => {
-                match &buy_context {
-                    None => {
-                        return Err(SetupError::Settings(
-                            "no trade entity found for 'buy' (E00006ac)",
-                        )
-                        .into());
-                    }
-                    Some(d) => {
-                        if variables.check_gas_fees {
-                            // todo: we are only picking up the first item from here. we need to improve the validation here
-                            if let Some(i) = d.first() {
-                                let v = CheckGasFeesBalanceVariables {
-                                    account_address_h160: variables.account_address_h160,
-                                    buy_approve_gas_price: i.scheme.approve_gas_price.to_owned(),
-                                    buy_approve_gas_limit: i.scheme.approve_gas_limit,
-                                    buy_gas_price: Some(i.scheme.gas_price.to_owned()),
-                                    buy_gas_limit: Some(i.scheme.gas_limit),
-                                    buy_retry_attempts: i.scheme.retry_attempts,
-                                    buy_perc_increase_gas_price: i.scheme.perc_increase_gas_price,
-
-                                    sell_approve_gas_price: None,
-                                    sell_gas_price: None,
-                                    sell_gas_limit: None,
-                                    sell_approve_gas_limit: None,
-                                    sell_retry_attempts: None,
-                                    sell_perc_increase_gas_price: None,
-                                    native_token_symbol: variables.native_token_symbol.to_owned(),
-                                };
-
-                                TradeValidation::check_gas_fees_balance(middleware, &v).await?;
-                            }
-                        }
-                    }
+                if buy_bucket.is_empty() {
+                    return Err(
+                        SetupError::Settings("no trade entity found for 'buy' (E00006ac)").into(),
+                    );
+                }
+
+                for buy_ctx in &buy_bucket {
+                    gas_fees_variables.push(Self::gas_fees_variables(
+                        Some(buy_ctx.as_ref()),
+                        None,
+                        variables,
+                    ));
                 }
             }
             TradeType::Sell => {
-                match &sell_context {
-                    None => {
-                        return Err(SetupError::Settings(
-                            "no trade entity found for 'sell' (E00006ad)",
-                        )
-                        .into());
-                    }
-                    Some(d) => {
-                        if variables.check_gas_fees {
-                            // todo: we are only picking up the first item from here. we need to improve the validation here
-                            if let Some(i) = d.first() {
-                                let v = CheckGasFeesBalanceVariables {
-                                    account_address_h160: variables.account_address_h160,
-                                    sell_approve_gas_price: i.scheme.approve_gas_price.to_owned(),
-                                    sell_approve_gas_limit: i.scheme.approve_gas_limit,
-                                    sell_gas_price: Some(i.scheme.gas_price.to_owned()),
-                                    sell_gas_limit: Some(i.scheme.gas_limit),
-                                    sell_retry_attempts: i.scheme.retry_attempts,
-                                    sell_perc_increase_gas_price: i.scheme.perc_increase_gas_price,
-
-                                    buy_approve_gas_price: None,
-                                    buy_gas_price: None,
-                                    buy_gas_limit: None,
-                                    buy_approve_gas_limit: None,
-                                    buy_retry_attempts: None,
-                                    buy_perc_increase_gas_price: None,
-
-                                    native_token_symbol: variables.native_token_symbol.to_owned(),
-                                };
-
-                                TradeValidation::check_gas_fees_balance(middleware, &v).await?;
-                            }
-                        }
-                    }
+                if sell_bucket.is_empty() {
+                    return Err(SetupError::Settings(
+                        "no trade entity found for 'sell' (E00006ad)",
+                    )
+                    .into());
+                }
+
+                for sell_ctx in &sell_bucket {
+                    gas_fees_variables.push(Self::gas_fees_variables(
+                        None,
+                        Some(sell_ctx.as_ref()),
+                        variables,
+                    ));
                 }
             }
+            TradeType::TokenTransfer => {}
+            TradeType::CancelTx => {}
+        }
+
+        if variables.check_gas_fees {
+            TradeValidation::check_gas_fees_balance(middleware, &gas_fees_variables).await?;
+        }
+
+        if let Some(b) = budget {
+            let planned_gas_fees =
+                TradeValidation::session_planned_gas_fees(middleware, &gas_fees_variables).await?;
+
+            TradeValidation::check_budget(
+                b,
+                &variables.network_name,
+                variables.account_address_h160,
+                &variables.native_token_symbol,
+                &Self::budget_variables(&buy_bucket, &sell_bucket),
+                planned_gas_fees,
+            )?;
         }
 
+        let buy_context = if buy_bucket.is_empty() {
+            None
+        } else {
+            Some(buy_bucket)
+        };
+        let sell_context = if sell_bucket.is_empty() {
+            None
+        } else {
+            Some(sell_bucket)
+        };
+
         Ok((buy_context, sell_context))
     }
 
     async fn feature(
         selected_config_network: &NetworkEntity,
+        selected_secrets_network_accounts: &[Accounts],
         entry_points: &EntryPoints,
         q_variables: &QuantVariables,
         q_middleware: &QuantMiddleware,
     ) -> anyhow::Result<Option<QuantFeature>> {
         let mut feature: Option<QuantFeature> = None;
 
+        if entry_points.trade_type == TradeType::CancelTx {
+            return Ok(Some(QuantFeature::CancelTx));
+        }
+
+        if entry_points.trade_type == TradeType::TokenTransfer {
+            if let Some(token_transfers) = &selected_config_network.feature.token_transfers {
+                let transfer_context = Self::transfer_contexts(
+                    token_transfers,
+                    selected_secrets_network_accounts,
+                    q_middleware,
+                )
+                .await?;
+
+                let q_token_transfer = QuantTokenTransfer { transfer_context };
+
+                feature = Some(QuantFeature::TokenTransfers(q_token_transfer));
+            }
+
+            return Ok(feature);
+        }
+
         if let Some(trades) = &selected_config_network.feature.trades {
-            let (buy_context, sell_context) =
-                Self::trading_contexts(entry_points, trades, q_variables, q_middleware).await?;
+            let (buy_context, sell_context) = Self::trading_contexts(
+                entry_points,
+                trades,
+                selected_config_network.budget.as_ref(),
+                q_variables,
+                q_middleware,
+            )
+            .await?;
 
             let q_trading = QuantTrading {
                 buy_context,
@@ -584,11 +720,13 @@ impl<'a> Quant {
             Some(d) => &d.exchange.networks,
         };
 
+        let mut selected_secrets_network_accounts: &[Accounts] = &[];
         let mut selected_secrets_network_account_option: Option<&secrets::AccountEntity> = None;
         let mut selected_config_network_option: Option<&config::NetworkEntity> = None;
 
         'secrets_network_iteration: for sn in selected_exchange_networks {
             if sn.network.name == entry_points.network_name {
+                selected_secrets_network_accounts = &sn.network.accounts;
                 selected_secrets_network_account_option = match sn.network.accounts.first() {
                     None => {
                         return Err(SetupError::Settings(
@@ -640,6 +778,7 @@ impl<'a> Quant {
 
         let q_feature_option = Self::feature(
             selected_config_network,
+            selected_secrets_network_accounts,
             entry_points,
             &q_variables,
             &q_middleware,
@@ -659,6 +798,8 @@ impl<'a> Quant {
             middleware: q_middleware,
             provider: q_provider,
             feature: q_feature,
+            ledger_lock: Mutex::new(()),
+            interrupter: StdinInterrupter::new(),
         };
 
         Ok(q)
@@ -4,10 +4,19 @@ use std::ops::Add;
 use ethers::abi::ethereum_types::{Address, U256};
 use qd::Quad;
 
+use crate::common::errors::TradingError;
 use crate::common::helpers::ethers::{get_account_balance, get_network_gas_price};
-use crate::common::models::config::ProviderEntity;
-use crate::common::models::trade_scheme::{SellScheme, TradeActuatorForSell, TradeName, TradeScheme, TradeSchemeVariant};
-use crate::common::utils::ether::ether_to_human_display;
+use crate::common::helpers::gas::auto_gas_price;
+use crate::common::models::config::{Budget, NetworkName, ProviderEntity};
+use crate::common::models::ledger::Ledger;
+use crate::common::models::scheme_helpers::SchemeHelpers;
+use crate::common::models::secrets::Accounts;
+use crate::common::models::token_transfer_scheme::TokenTransfer;
+use crate::common::models::trade_scheme::{GasLimit, SellScheme, TakeProfitStep, TradeActuatorForSell, TradeName, TradeScheme, TradeSchemeVariant};
+use crate::common::utils::ether::{
+    decimals_to_units, ether_to_human_display, units_to_human_display,
+};
+use crate::common::utils::f256::to_f256;
 use crate::controllers::cli::entry_points::TradeType;
 use crate::controllers::middleware::QuantMiddleware;
 use crate::controllers::quant::trade_validation_helper::{
@@ -24,6 +33,10 @@ pub struct CheckGasFeesBalanceVariables {
     pub buy_gas_limit: Option<u64>,
     pub buy_retry_attempts: Option<u64>,
     pub buy_perc_increase_gas_price: Option<u32>,
+    pub buy_gas_price_multiplier_perc: Option<u32>,
+    pub buy_min_gas_price: Option<String>,
+    pub buy_max_gas_price: Option<String>,
+    pub buy_native_amount_of_token_in: Option<String>,
 
     pub sell_approve_gas_price: Option<String>,
     pub sell_approve_gas_limit: Option<u64>,
@@ -31,22 +44,34 @@ pub struct CheckGasFeesBalanceVariables {
     pub sell_gas_limit: Option<u64>,
     pub sell_retry_attempts: Option<u64>,
     pub sell_perc_increase_gas_price: Option<u32>,
+    pub sell_gas_price_multiplier_perc: Option<u32>,
+    pub sell_min_gas_price: Option<String>,
+    pub sell_max_gas_price: Option<String>,
     pub native_token_symbol: String,
 }
 
+/// the [token_in] planned to be spent by a single Buy or Sell function.
+/// [amount_of_token_in] isn't known before trading when a Sell uses 'perc_of_token_in'
+pub struct CheckBudgetVariables {
+    pub token_in_h160: Address,
+    pub token_in_decimals: u8,
+    pub amount_of_token_in: Option<String>,
+}
+
 impl TradeValidation {
     // [gas_price], [max_gas_price] and [perc_increase_gas_price]
     fn check_gas_price(
         trade_scheme: &TradeScheme,
         perc_increase_gas_price: Option<u32>,
         max_gas_price_gwei: Option<U256>,
-        gas_price_gwei: U256,
+        gas_price_gwei: Option<U256>,
     ) -> anyhow::Result<()> {
         if perc_increase_gas_price.is_some() && max_gas_price_gwei.is_none() {
             paniq!("config file error in {}:\n\n'max_gas_price' is mandatory when 'perc_increase_gas_price' is active (P00015a)", trade_scheme)
         }
 
-        if let Some(price) = max_gas_price_gwei {
+        // the 'auto' gas price is capped at [max_gas_price]
+        if let (Some(price), Some(gas_price_gwei)) = (max_gas_price_gwei, gas_price_gwei) {
             if gas_price_gwei > price {
                 paniq!("config file error in {}:\n\n'max_gas_price' should be greater than or equal to 'gas_price' (P00015c)", trade_scheme)
             }
@@ -55,11 +80,64 @@ impl TradeValidation {
         Ok(())
     }
 
+    // [gas_price] 'auto', [gas_price_multiplier_perc] and [min_gas_price]
+    fn check_auto_gas_price(
+        trade_scheme: &TradeScheme,
+        is_auto_gas_price_active: bool,
+        gas_price_multiplier_perc: Option<u32>,
+        min_gas_price_gwei: Option<U256>,
+        max_gas_price_gwei: Option<U256>,
+    ) -> anyhow::Result<()> {
+        if !is_auto_gas_price_active {
+            if gas_price_multiplier_perc.is_some() || min_gas_price_gwei.is_some() {
+                paniq!("config file error in {}:\n\n'gas_price_multiplier_perc' and 'min_gas_price' can only be used along with the 'auto' gas price (P00033a)", trade_scheme)
+            }
+
+            return Ok(());
+        }
+
+        if gas_price_multiplier_perc == Some(0) {
+            paniq!("config file error in {}:\n\n'gas_price_multiplier_perc' should be greater than 0 (P00033b)", trade_scheme)
+        }
+
+        if let (Some(min), Some(max)) = (min_gas_price_gwei, max_gas_price_gwei) {
+            if min > max {
+                paniq!("config file error in {}:\n\n'max_gas_price' should be greater than or equal to 'min_gas_price' (P00033c)", trade_scheme)
+            }
+        }
+
+        Ok(())
+    }
+
+    // [slippage], [max_slippage] and [perc_increase_slippage]
+    fn check_slippage(
+        trade_scheme: &TradeScheme,
+        slippage: u8,
+        perc_increase_slippage: Option<u32>,
+        max_slippage: Option<u8>,
+    ) -> anyhow::Result<()> {
+        if perc_increase_slippage.is_some() && max_slippage.is_none() {
+            paniq!("config file error in {}:\n\n'max_slippage' is mandatory when 'perc_increase_slippage' is active (P00032a)", trade_scheme)
+        }
+
+        if let Some(max) = max_slippage {
+            if slippage > max {
+                paniq!("config file error in {}:\n\n'max_slippage' should be greater than or equal to 'slippage' (P00032b)", trade_scheme)
+            }
+
+            if max > 100 {
+                paniq!("config file error in {}:\n\n'max_slippage' should be less than or equal to 100 (P00032c)", trade_scheme)
+            }
+        }
+
+        Ok(())
+    }
+
     // [approve_gas_price] and [approve_gas_limit]
     fn check_approve_gas_fees(
         trade_scheme: &TradeScheme,
         approve_gas_price: Option<String>,
-        approve_gas_limit: Option<u64>,
+        approve_gas_limit: Option<GasLimit>,
     ) -> anyhow::Result<()> {
         if approve_gas_price.is_some() && approve_gas_limit.is_none() {
             paniq!("config file error in {}:\n\n'approve_gas_limit' is mandatory when 'approve_gas_price' is active (P00019a)", trade_scheme)
@@ -68,16 +146,275 @@ impl TradeValidation {
         Ok(())
     }
 
+    // [gas_limit] 'auto', [approve_gas_limit] 'auto', [gas_limit_margin_perc], [max_gas_limit] and [max_approve_gas_limit]
+    fn check_auto_gas_limit(
+        trade_scheme: &TradeScheme,
+        gas_limit: &GasLimit,
+        approve_gas_limit: &Option<GasLimit>,
+        gas_limit_margin_perc: Option<u32>,
+        max_gas_limit: Option<u64>,
+        max_approve_gas_limit: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let is_auto_gas_limit_active = gas_limit.is_auto();
+        let is_auto_approve_gas_limit_active = match approve_gas_limit {
+            None => false,
+            Some(d) => d.is_auto(),
+        };
+
+        if gas_limit.fixed().is_none() && !is_auto_gas_limit_active {
+            paniq!("config file error in {}:\n\n'gas_limit' should be either a number or 'auto' (P00034a)", trade_scheme)
+        }
+
+        if let Some(d) = approve_gas_limit {
+            if d.fixed().is_none() && !is_auto_approve_gas_limit_active {
+                paniq!("config file error in {}:\n\n'approve_gas_limit' should be either a number or 'auto' (P00034a)", trade_scheme)
+            }
+        }
+
+        // the max gas limit is used when the gas estimation fails
+        if is_auto_gas_limit_active && max_gas_limit.is_none() {
+            paniq!("config file error in {}:\n\n'max_gas_limit' is mandatory when 'gas_limit' is 'auto' (P00034b)", trade_scheme)
+        }
+
+        if is_auto_approve_gas_limit_active && max_approve_gas_limit.is_none() {
+            paniq!("config file error in {}:\n\n'max_approve_gas_limit' is mandatory when 'approve_gas_limit' is 'auto' (P00034c)", trade_scheme)
+        }
+
+        if !is_auto_gas_limit_active && max_gas_limit.is_some() {
+            paniq!("config file error in {}:\n\n'max_gas_limit' can only be used along with the 'auto' gas limit (P00034d)", trade_scheme)
+        }
+
+        if !is_auto_approve_gas_limit_active && max_approve_gas_limit.is_some() {
+            paniq!("config file error in {}:\n\n'max_approve_gas_limit' can only be used along with the 'auto' approve gas limit (P00034d)", trade_scheme)
+        }
+
+        if !is_auto_gas_limit_active
+            && !is_auto_approve_gas_limit_active
+            && gas_limit_margin_perc.is_some()
+        {
+            paniq!("config file error in {}:\n\n'gas_limit_margin_perc' can only be used along with the 'auto' gas limit (P00034d)", trade_scheme)
+        }
+
+        Ok(())
+    }
+
+    // [snipe_liquidity_from_mempool], [wait_time_before_first_tx_attempt_in_ms], [path] and [auto_route]
+    fn check_mempool_sniping(
+        trade_scheme: &TradeScheme,
+        snipe_liquidity_from_mempool: Option<bool>,
+        wait_time_before_first_tx_attempt_in_ms: Option<i64>,
+        path: &Option<Vec<String>>,
+        auto_route: Option<bool>,
+        amount_of_token_out: &Option<String>,
+    ) -> anyhow::Result<()> {
+        if !snipe_liquidity_from_mempool.unwrap_or(false) {
+            return Ok(());
+        }
+
+        if wait_time_before_first_tx_attempt_in_ms.is_some() {
+            paniq!("config file error in {}:\n\n'wait_time_before_first_tx_attempt_in_ms' cannot be used along with 'snipe_liquidity_from_mempool' (P00022a)", trade_scheme)
+        }
+
+        if path.is_some() || auto_route.unwrap_or(false) {
+            paniq!("config file error in {}:\n\n'path' and 'auto_route' cannot be used along with 'snipe_liquidity_from_mempool' (P00022b)", trade_scheme)
+        }
+
+        if amount_of_token_out.is_some() {
+            paniq!("config file error in {}:\n\n'amount_of_token_out' cannot be used along with 'snipe_liquidity_from_mempool' (P00022c)", trade_scheme)
+        }
+
+        Ok(())
+    }
+
+    // [amount_of_token_out], [is_token_out_deflationary] and [auto_detect_tax]
+    fn check_exact_output(
+        trade_scheme: &TradeScheme,
+        amount_of_token_out: &Option<String>,
+        is_token_out_deflationary: bool,
+        auto_detect_tax: Option<bool>,
+    ) -> anyhow::Result<()> {
+        // the exact output swap functions of the router do not support fee on transfer tokens
+        if amount_of_token_out.is_some() && is_token_out_deflationary {
+            paniq!("config file error in {}:\n\n'amount_of_token_out' cannot be used when 'is_token_out_deflationary' is true (P00024a)", trade_scheme)
+        }
+
+        if amount_of_token_out.is_some() && auto_detect_tax.unwrap_or(false) {
+            paniq!("config file error in {}:\n\n'amount_of_token_out' cannot be used along with 'auto_detect_tax' (P00024b)", trade_scheme)
+        }
+
+        Ok(())
+    }
+
+    // [trailing_stop_loss_perc] and [stop_loss_floor_price]
+    fn check_trailing_stop_loss(
+        trade_scheme: &TradeScheme,
+        trailing_stop_loss_perc: Option<u8>,
+        stop_loss_floor_price: &Option<String>,
+    ) -> anyhow::Result<()> {
+        match trailing_stop_loss_perc {
+            None => {
+                if stop_loss_floor_price.is_some() {
+                    paniq!("config file error in {}:\n\n'trailing_stop_loss_perc' is mandatory when 'stop_loss_floor_price' is active (P00026b)", trade_scheme)
+                }
+            }
+            Some(p) => {
+                if p == 0 || p >= 100 {
+                    paniq!("config file error in {}:\n\n'trailing_stop_loss_perc' should be between 1 and 99 (P00026a)", trade_scheme)
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // [take_profit_ladder]
+    fn check_take_profit_ladder(
+        trade_scheme: &TradeScheme,
+        take_profit_ladder: &Option<Vec<TakeProfitStep>>,
+    ) -> anyhow::Result<()> {
+        let ladder = match take_profit_ladder {
+            None => return Ok(()),
+            Some(d) => d,
+        };
+
+        if ladder.is_empty() {
+            paniq!("config file error in {}:\n\n'take_profit_ladder' should contain atleast one step, leave it empty to turn it off (P00027a)", trade_scheme)
+        }
+
+        let mut total_perc_of_position = 0_u32;
+
+        for step in ladder {
+            if step.trade_at_price.is_some() == step.trade_at_profit_perc.is_some() {
+                paniq!("config file error in {}:\n\nonly either one of 'trade_at_price' or 'trade_at_profit_perc' is required in every step of the 'take_profit_ladder' (P00027b)", trade_scheme)
+            }
+
+            if step.perc_of_position == 0 {
+                paniq!("config file error in {}:\n\n'perc_of_position' of a 'take_profit_ladder' step should be greater than 0 (P00027c)", trade_scheme)
+            }
+
+            total_perc_of_position += step.perc_of_position as u32;
+        }
+
+        if total_perc_of_position > 100 {
+            paniq!("config file error in {}:\n\nthe total 'perc_of_position' of the 'take_profit_ladder' cannot be greater than 100 percentage (P00027d)", trade_scheme)
+        }
+
+        Ok(())
+    }
+
+    // [max_price_impact_perc] and [abort_on_max_price_impact]
+    fn check_price_impact(
+        trade_scheme: &TradeScheme,
+        max_price_impact_perc: Option<u8>,
+        abort_on_max_price_impact: Option<bool>,
+    ) -> anyhow::Result<()> {
+        match max_price_impact_perc {
+            None => {
+                if abort_on_max_price_impact.is_some() {
+                    paniq!("config file error in {}:\n\n'max_price_impact_perc' is mandatory when 'abort_on_max_price_impact' is active (P00028b)", trade_scheme)
+                }
+            }
+            Some(p) => {
+                if p == 0 || p > 100 {
+                    paniq!("config file error in {}:\n\n'max_price_impact_perc' should be between 1 and 100 (P00028a)", trade_scheme)
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // [rug_pull_liquidity_drop_perc] and [rug_pull_perc_increase_gas_price]
+    fn check_rug_pull_watcher(
+        trade_scheme: &TradeScheme,
+        rug_pull_liquidity_drop_perc: Option<u8>,
+        rug_pull_perc_increase_gas_price: Option<u32>,
+    ) -> anyhow::Result<()> {
+        match rug_pull_liquidity_drop_perc {
+            None => {
+                if rug_pull_perc_increase_gas_price.is_some() {
+                    paniq!("config file error in {}:\n\n'rug_pull_liquidity_drop_perc' is mandatory when 'rug_pull_perc_increase_gas_price' is active (P00030b)", trade_scheme)
+                }
+            }
+            Some(p) => {
+                if p == 0 || p > 100 {
+                    paniq!("config file error in {}:\n\n'rug_pull_liquidity_drop_perc' should be between 1 and 100 (P00030a)", trade_scheme)
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // [risk_scan] and [max_risk_score]
+    fn check_risk_scan(
+        trade_scheme: &TradeScheme,
+        risk_scan: Option<bool>,
+        max_risk_score: Option<u8>,
+    ) -> anyhow::Result<()> {
+        if let Some(m) = max_risk_score {
+            if !risk_scan.unwrap_or(false) {
+                paniq!("config file error in {}:\n\n'risk_scan' is mandatory when 'max_risk_score' is active (P00031b)", trade_scheme)
+            }
+
+            if m > 100 {
+                paniq!("config file error in {}:\n\n'max_risk_score' should not be above 100 (P00031a)", trade_scheme)
+            }
+        }
+
+        Ok(())
+    }
+
+    // [honeypot_max_loss_perc]
+    fn check_honeypot(
+        trade_scheme: &TradeScheme,
+        honeypot_max_loss_perc: Option<u8>,
+    ) -> anyhow::Result<()> {
+        if let Some(p) = honeypot_max_loss_perc {
+            if p > 100 {
+                paniq!("config file error in {}:\n\n'honeypot_max_loss_perc' should not be above 100 (P00025a)", trade_scheme)
+            }
+        }
+
+        Ok(())
+    }
+
+    // [path] and [auto_route]
+    fn check_swap_route(
+        trade_scheme: &TradeScheme,
+        path: &Option<Vec<String>>,
+        auto_route: Option<bool>,
+    ) -> anyhow::Result<()> {
+        if let Some(p) = path {
+            if p.is_empty() {
+                paniq!("config file error in {}:\n\n'path' should contain atleast one token contract, leave it empty to swap directly (P00023b)", trade_scheme)
+            }
+
+            if auto_route.unwrap_or(false) {
+                paniq!("config file error in {}:\n\nonly either one of 'path' or 'auto_route' is allowed (P00023a)", trade_scheme)
+            }
+        }
+
+        Ok(())
+    }
+
     // Check if the wallet has enough base token to spend on the gas
+    // the gas fees of every trade entry are added up since all of them are carried out using the same account
     pub async fn check_gas_fees_balance(
         middleware: &QuantMiddleware,
-        v: &CheckGasFeesBalanceVariables,
+        gas_fees_variables: &[CheckGasFeesBalanceVariables],
     ) -> anyhow::Result<()> {
         log::debug!("initializing gas fees checker...");
 
+        let first_variables = match gas_fees_variables.first() {
+            None => return Ok(()),
+            Some(d) => d,
+        };
+        let native_token_symbol = first_variables.native_token_symbol.to_owned();
+
         log::debug!("fetching account balance...");
         let account_balance =
-            get_account_balance(&middleware.client, &v.account_address_h160).await?;
+            get_account_balance(&middleware.client, &first_variables.account_address_h160).await?;
 
         log::debug!("fetching the network gas price...");
         let network_gas_price = get_network_gas_price(&middleware.client).await?;
@@ -85,13 +422,228 @@ impl TradeValidation {
         let mut min_required_gas_price_human_readable_format: Quad = Quad::from(0);
         let mut active_gas_fees_variables: Vec<String> = vec![];
 
+        for v in gas_fees_variables {
+            let (entry_min_required_gas_fees, mut entry_active_gas_fees_variables) =
+                Self::min_required_gas_fees(network_gas_price, v)?;
+
+            min_required_gas_price_human_readable_format =
+                min_required_gas_price_human_readable_format.add(entry_min_required_gas_fees);
+            active_gas_fees_variables.append(&mut entry_active_gas_fees_variables);
+        }
+
+        active_gas_fees_variables.sort();
+        active_gas_fees_variables.dedup();
+
+        let account_balance_human_readable = ether_to_human_display(account_balance);
+
+        log::info!(
+            "account balance: {} {}",
+            account_balance_human_readable,
+            native_token_symbol
+        );
+
+        log::debug!(
+            "minimum gas required : {} {}",
+            min_required_gas_price_human_readable_format,
+            native_token_symbol
+        );
+
+        // if the account balance is lower than the minimum required gas price then return an error
+        if min_required_gas_price_human_readable_format > account_balance_human_readable {
+            paniq!("config file error: you need atleast {} {} in your wallet to spend on the gas fees (P00020a).\nyou only have {} {} as your wallet balance.\nactive gas variables in the config file: \"{}\"", min_required_gas_price_human_readable_format, native_token_symbol, account_balance_human_readable, native_token_symbol, active_gas_fees_variables.join(", "));
+        }
+
+        Ok(())
+    }
+
+    // the gas fees planned to be spent by every trade entry of the session
+    pub async fn session_planned_gas_fees(
+        middleware: &QuantMiddleware,
+        gas_fees_variables: &[CheckGasFeesBalanceVariables],
+    ) -> anyhow::Result<Quad> {
+        log::debug!("fetching the network gas price...");
+        let network_gas_price = get_network_gas_price(&middleware.client).await?;
+
+        let mut planned_gas_fees_human_readable_format: Quad = Quad::from(0);
+
+        for v in gas_fees_variables {
+            let (entry_planned_gas_fees, _) = Self::planned_gas_fees(network_gas_price, v)?;
+
+            planned_gas_fees_human_readable_format =
+                planned_gas_fees_human_readable_format.add(entry_planned_gas_fees);
+        }
+
+        Ok(planned_gas_fees_human_readable_format)
+    }
+
+    // the planned spends of the session and the spends of the account in the rolling 24 hours recorded in the ledger
+    // are checked against the budget of the network
+    pub fn check_budget(
+        budget: &Budget,
+        network_name: &NetworkName,
+        account_address_h160: Address,
+        native_token_symbol: &str,
+        budget_variables: &[CheckBudgetVariables],
+        planned_gas_fees: Quad,
+    ) -> anyhow::Result<()> {
+        log::debug!("initializing budget checker...");
+
+        let ledger = Ledger::load(&Ledger::file_path(network_name))?;
+        let window_start = Ledger::window_start();
+
+        log::debug!(
+            "gas fees planned to be spent: {} {}",
+            planned_gas_fees,
+            native_token_symbol
+        );
+
+        if let Some(max_gas_spend_per_run) = &budget.max_gas_spend_per_run {
+            if planned_gas_fees > to_f256(max_gas_spend_per_run.as_str()) {
+                log::error!("the trades are planning to spend {} {} on the gas fees which is above 'max_gas_spend_per_run' ({} {}) of the budget", planned_gas_fees, native_token_symbol, max_gas_spend_per_run, native_token_symbol);
+
+                return Err(TradingError::Budget(
+                    "the planned gas fees are above 'max_gas_spend_per_run'",
+                )
+                .into());
+            }
+        }
+
+        if let Some(max_gas_spend_per_day) = &budget.max_gas_spend_per_day {
+            let gas_spent =
+                ether_to_human_display(ledger.gas_spent_since(account_address_h160, window_start));
+
+            log::info!(
+                "gas fees spent in the last 24 hours: {} {}",
+                gas_spent,
+                native_token_symbol
+            );
+
+            if gas_spent.add(planned_gas_fees) > to_f256(max_gas_spend_per_day.as_str()) {
+                log::error!("the trades are planning to spend {} {} on the gas fees while {} {} were already spent in the last 24 hours, which is above 'max_gas_spend_per_day' ({} {}) of the budget", planned_gas_fees, native_token_symbol, gas_spent, native_token_symbol, max_gas_spend_per_day, native_token_symbol);
+
+                return Err(TradingError::Budget("the planned and the spent gas fees of the last 24 hours are above 'max_gas_spend_per_day'").into());
+            }
+        }
+
+        let token_budgets = match &budget.token_budgets {
+            None => return Ok(()),
+            Some(d) => d,
+        };
+
+        for b in token_budgets {
+            let token_budget = &b.token_budget;
+            let token_h160 = token_budget.token_h160()?;
+
+            let planned_spends: Vec<&CheckBudgetVariables> = budget_variables
+                .iter()
+                .filter(|d| d.token_in_h160 == token_h160)
+                .collect();
+
+            // the budget doesn't apply to any of the trades
+            let token_in_decimals = match planned_spends.first() {
+                None => continue,
+                Some(d) => d.token_in_decimals,
+            };
+
+            let mut planned_spend_u256 = U256::zero();
+            for v in &planned_spends {
+                match &v.amount_of_token_in {
+                    Some(amount_of_token_in) => {
+                        planned_spend_u256 = planned_spend_u256
+                            .add(decimals_to_units(amount_of_token_in, token_in_decimals)?);
+                    }
+                    // a Sell using 'perc_of_token_in' is exempt from the planned spends,
+                    // its swap is still recorded in the ledger and counted by the later runs
+                    None => log::warn!(
+                        "a Sell of {} using 'perc_of_token_in' isn't counted in the planned spends of the budget",
+                        token_budget.token_symbol
+                    ),
+                }
+            }
+
+            if let Some(max_spend_per_run) = &token_budget.max_spend_per_run {
+                let max_spend_per_run_u256 =
+                    decimals_to_units(max_spend_per_run, token_in_decimals)?;
+
+                if planned_spend_u256 > max_spend_per_run_u256 {
+                    log::error!("the trades are planning to spend {} {} which is above 'max_spend_per_run' ({} {}) of the budget", units_to_human_display(planned_spend_u256, token_in_decimals), token_budget.token_symbol, max_spend_per_run, token_budget.token_symbol);
+
+                    return Err(TradingError::Budget(
+                        "the planned spends are above 'max_spend_per_run'",
+                    )
+                    .into());
+                }
+            }
+
+            if let Some(max_spend_per_day) = &token_budget.max_spend_per_day {
+                let max_spend_per_day_u256 =
+                    decimals_to_units(max_spend_per_day, token_in_decimals)?;
+                let spent_u256 =
+                    ledger.token_in_spent_since(account_address_h160, token_h160, window_start);
+
+                log::info!(
+                    "{} spent in the last 24 hours: {}",
+                    token_budget.token_symbol,
+                    units_to_human_display(spent_u256, token_in_decimals)
+                );
+
+                if spent_u256.add(planned_spend_u256) > max_spend_per_day_u256 {
+                    log::error!("the trades are planning to spend {} {} while {} {} were already spent in the last 24 hours, which is above 'max_spend_per_day' ({} {}) of the budget", units_to_human_display(planned_spend_u256, token_in_decimals), token_budget.token_symbol, units_to_human_display(spent_u256, token_in_decimals), token_budget.token_symbol, max_spend_per_day, token_budget.token_symbol);
+
+                    return Err(TradingError::Budget("the planned and the spent amounts of the last 24 hours are above 'max_spend_per_day'").into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // the 'auto' gas price is resolved from the network gas price at the time of the check
+    fn resolve_gas_price(
+        network_gas_price: U256,
+        gas_price: &Option<String>,
+        gas_price_multiplier_perc: Option<u32>,
+        min_gas_price: &Option<String>,
+        max_gas_price: &Option<String>,
+    ) -> anyhow::Result<Option<String>> {
+        let p = match gas_price {
+            Some(p) if SchemeHelpers::is_auto_keyword(p) => p,
+            _ => return Ok(gas_price.to_owned()),
+        };
+
+        let (gas_price, _) = auto_gas_price(
+            network_gas_price,
+            gas_price_multiplier_perc,
+            SchemeHelpers::decimals_price_to_gwei_option(min_gas_price)?,
+            SchemeHelpers::decimals_price_to_gwei_option(max_gas_price)?,
+        );
+
+        log::debug!("'{}' gas price resolved to: {} gwei", p, gas_price);
+
+        Ok(Some(gas_price.to_string()))
+    }
+
+    // the gas fees planned to be spent by the Buy and the Sell functions of a single trade entry
+    fn planned_gas_fees(
+        network_gas_price: U256,
+        v: &CheckGasFeesBalanceVariables,
+    ) -> anyhow::Result<(Quad, Vec<String>)> {
+        let mut planned_gas_fees_human_readable_format: Quad = Quad::from(0);
+        let mut active_gas_fees_variables: Vec<String> = vec![];
+
         let buy_calculate_gas_price_args = CalculateGasPriceArgs {
             trade_name: TradeName::Buy,
             network_gas_price,
             native_token_symbol: v.native_token_symbol.to_owned(),
             approve_gas_price: v.buy_approve_gas_price.to_owned(),
             approve_gas_limit: v.buy_approve_gas_limit,
-            gas_price: v.buy_gas_price.to_owned(),
+            gas_price: Self::resolve_gas_price(
+                network_gas_price,
+                &v.buy_gas_price,
+                v.buy_gas_price_multiplier_perc,
+                &v.buy_min_gas_price,
+                &v.buy_max_gas_price,
+            )?,
             gas_limit: v.buy_gas_limit,
             retry_attempts: v.buy_retry_attempts,
             perc_increase_gas_price: v.buy_perc_increase_gas_price,
@@ -107,7 +659,13 @@ impl TradeValidation {
             native_token_symbol: v.native_token_symbol.to_owned(),
             approve_gas_price: v.sell_approve_gas_price.to_owned(),
             approve_gas_limit: v.sell_approve_gas_limit,
-            gas_price: v.sell_gas_price.to_owned(),
+            gas_price: Self::resolve_gas_price(
+                network_gas_price,
+                &v.sell_gas_price,
+                v.sell_gas_price_multiplier_perc,
+                &v.sell_min_gas_price,
+                &v.sell_max_gas_price,
+            )?,
             gas_limit: v.sell_gas_limit,
             retry_attempts: v.sell_retry_attempts,
             perc_increase_gas_price: v.sell_perc_increase_gas_price,
@@ -116,32 +674,43 @@ impl TradeValidation {
         let (sell_min_required_gas_price_human_readable_format, mut sell_active_gas_fees_variables) =
             TradeValidationHelper::calculate_gas_price(sell_calculate_gas_price_args)?;
 
-        min_required_gas_price_human_readable_format = min_required_gas_price_human_readable_format
+        planned_gas_fees_human_readable_format = planned_gas_fees_human_readable_format
             .add(buy_min_required_gas_price_human_readable_format)
             .add(sell_min_required_gas_price_human_readable_format);
         active_gas_fees_variables.append(&mut buy_active_gas_fees_variables);
         active_gas_fees_variables.append(&mut sell_active_gas_fees_variables);
 
-        let account_balance_human_readable = ether_to_human_display(account_balance);
+        Ok((
+            planned_gas_fees_human_readable_format,
+            active_gas_fees_variables,
+        ))
+    }
 
-        log::info!(
-            "account balance: {} {}",
-            account_balance_human_readable,
-            v.native_token_symbol
-        );
+    // the minimum amount of native token required by the Buy and the Sell functions of a single trade entry
+    fn min_required_gas_fees(
+        network_gas_price: U256,
+        v: &CheckGasFeesBalanceVariables,
+    ) -> anyhow::Result<(Quad, Vec<String>)> {
+        let (mut min_required_gas_price_human_readable_format, mut active_gas_fees_variables) =
+            Self::planned_gas_fees(network_gas_price, v)?;
 
-        log::debug!(
-            "minimum gas required : {} {}",
-            min_required_gas_price_human_readable_format,
-            v.native_token_symbol
-        );
+        // the native coin spent by the Buy function
+        if let Some(a) = &v.buy_native_amount_of_token_in {
+            log::debug!(
+                "maximum amount of {} spent by the Buy function: {}",
+                v.native_token_symbol,
+                a
+            );
 
-        // if the account balance is lower than the minimum required gas price then return an error
-        if min_required_gas_price_human_readable_format > account_balance_human_readable {
-            paniq!("config file error: you need atleast {} {} in your wallet to spend on the gas fees (P00020a).\nyou only have {} {} as your wallet balance.\nactive gas variables in the config file: \"{}\"", min_required_gas_price_human_readable_format, v.native_token_symbol.to_owned(), account_balance_human_readable,v.native_token_symbol.to_owned(), active_gas_fees_variables.join(", "));
+            min_required_gas_price_human_readable_format =
+                min_required_gas_price_human_readable_format.add(to_f256(a.as_str()));
+            active_gas_fees_variables.push(format!("{}: 'amount_of_token_in'", TradeName::Buy));
         }
 
-        Ok(())
+        Ok((
+            min_required_gas_price_human_readable_format,
+            active_gas_fees_variables,
+        ))
     }
 
     // [perc_of_token_in] && [amount_of_token_in]
@@ -224,11 +793,33 @@ impl TradeValidation {
                     d.gas_price_gwei()?,
                 )?;
 
+                // [gas_price] 'auto', [gas_price_multiplier_perc] and [min_gas_price]
+                Self::check_auto_gas_price(
+                    s,
+                    d.is_auto_gas_price_active(),
+                    d.gas_price_multiplier_perc,
+                    d.min_gas_price_gwei()?,
+                    d.max_gas_price_gwei()?,
+                )?;
+
+                // [slippage], [max_slippage] and [perc_increase_slippage]
+                Self::check_slippage(s, d.slippage, d.perc_increase_slippage, d.max_slippage)?;
+
                 // [approve_gas_price] and [approve_gas_limit]
                 Self::check_approve_gas_fees(
                     s,
                     d.approve_gas_price.to_owned(),
-                    d.approve_gas_limit,
+                    d.approve_gas_limit.to_owned(),
+                )?;
+
+                // [gas_limit] 'auto', [approve_gas_limit] 'auto', [gas_limit_margin_perc], [max_gas_limit] and [max_approve_gas_limit]
+                Self::check_auto_gas_limit(
+                    s,
+                    &d.gas_limit,
+                    &d.approve_gas_limit,
+                    d.gas_limit_margin_perc,
+                    d.max_gas_limit,
+                    d.max_approve_gas_limit,
                 )?;
 
                 Self::trading_time(
@@ -238,6 +829,36 @@ impl TradeValidation {
                     d.time_between_retries_in_ms,
                     d.retry_attempts,
                 )?;
+
+                // [path] and [auto_route]
+                Self::check_swap_route(s, &d.path, d.auto_route)?;
+
+                // [max_price_impact_perc] and [abort_on_max_price_impact]
+                Self::check_price_impact(s, d.max_price_impact_perc, d.abort_on_max_price_impact)?;
+
+                // [snipe_liquidity_from_mempool]
+                Self::check_mempool_sniping(
+                    s,
+                    d.snipe_liquidity_from_mempool,
+                    d.wait_time_before_first_tx_attempt_in_ms,
+                    &d.path,
+                    d.auto_route,
+                    &d.amount_of_token_out,
+                )?;
+
+                // [amount_of_token_out]
+                Self::check_exact_output(
+                    s,
+                    &d.amount_of_token_out,
+                    d.is_token_out_deflationary,
+                    d.auto_detect_tax,
+                )?;
+
+                // [honeypot_max_loss_perc]
+                Self::check_honeypot(s, d.honeypot_max_loss_perc)?;
+
+                // [risk_scan] and [max_risk_score]
+                Self::check_risk_scan(s, d.risk_scan, d.max_risk_score)?;
             }
             TradeScheme::Sell(d) => {
                 // [perc_of_token_in] && [amount_of_token_in]
@@ -251,11 +872,33 @@ impl TradeValidation {
                     d.gas_price_gwei()?,
                 )?;
 
+                // [gas_price] 'auto', [gas_price_multiplier_perc] and [min_gas_price]
+                Self::check_auto_gas_price(
+                    s,
+                    d.is_auto_gas_price_active(),
+                    d.gas_price_multiplier_perc,
+                    d.min_gas_price_gwei()?,
+                    d.max_gas_price_gwei()?,
+                )?;
+
+                // [slippage], [max_slippage] and [perc_increase_slippage]
+                Self::check_slippage(s, d.slippage, d.perc_increase_slippage, d.max_slippage)?;
+
                 // [approve_gas_price] and [approve_gas_limit]
                 Self::check_approve_gas_fees(
                     s,
                     d.approve_gas_price.to_owned(),
-                    d.approve_gas_limit,
+                    d.approve_gas_limit.to_owned(),
+                )?;
+
+                // [gas_limit] 'auto', [approve_gas_limit] 'auto', [gas_limit_margin_perc], [max_gas_limit] and [max_approve_gas_limit]
+                Self::check_auto_gas_limit(
+                    s,
+                    &d.gas_limit,
+                    &d.approve_gas_limit,
+                    d.gas_limit_margin_perc,
+                    d.max_gas_limit,
+                    d.max_approve_gas_limit,
                 )?;
 
                 Self::trading_time(
@@ -265,6 +908,29 @@ impl TradeValidation {
                     d.time_between_retries_in_ms,
                     d.retry_attempts,
                 )?;
+
+                // [path] and [auto_route]
+                Self::check_swap_route(s, &d.path, d.auto_route)?;
+
+                // [max_price_impact_perc] and [abort_on_max_price_impact]
+                Self::check_price_impact(s, d.max_price_impact_perc, d.abort_on_max_price_impact)?;
+
+                // [trailing_stop_loss_perc] and [stop_loss_floor_price]
+                Self::check_trailing_stop_loss(
+                    s,
+                    d.trailing_stop_loss_perc,
+                    &d.stop_loss_floor_price,
+                )?;
+
+                // [take_profit_ladder]
+                Self::check_take_profit_ladder(s, &d.take_profit_ladder)?;
+
+                // [rug_pull_liquidity_drop_perc] and [rug_pull_perc_increase_gas_price]
+                Self::check_rug_pull_watcher(
+                    s,
+                    d.rug_pull_liquidity_drop_perc,
+                    d.rug_pull_perc_increase_gas_price,
+                )?;
             }
         }
 
@@ -287,6 +953,30 @@ impl TradeValidation {
         Ok(())
     }
 
+    // check and confirm whether in a BuySell trade the profit percentage trade actuator of Sell is comparable with the Buy entry price
+    // the Buy entry price is in 'token_in_contract' of Buy, hence the 'token_out_contract' of Sell needs to be the same
+    pub fn validate_buysell_profit_perc_tokens(
+        sell_scheme: &SellScheme,
+        buy_token_in_contract: Option<String>,
+        sell_token_out_contract: Option<String>,
+    ) -> anyhow::Result<()> {
+        let is_profit_perc_used = matches!(
+            sell_scheme.trade_actuator()?,
+            Some(TradeActuatorForSell::Percentage(_))
+        ) || sell_scheme.is_take_profit_ladder_perc_used();
+
+        if is_profit_perc_used {
+            let buy_token_in = buy_token_in_contract.map(|d| d.to_lowercase());
+            let sell_token_out = sell_token_out_contract.map(|d| d.to_lowercase());
+
+            if buy_token_in.is_none() || buy_token_in != sell_token_out {
+                paniq!("config file error: 'token_out_contract' of Sell and 'token_in_contract' of Buy needs to be the same when either of 'trade_at_profit_perc' and 'hold_trade_below_profit_perc' are used (P00014b)", )
+            }
+        }
+
+        Ok(())
+    }
+
     // check sell scheme actuator
     fn trade_actuator_sell_scheme(
         trade_scheme: &TradeScheme,
@@ -306,8 +996,16 @@ impl TradeValidation {
             trade_actuator_count += 1;
         }
 
+        if sell_scheme.trailing_stop_loss_perc.is_some() {
+            trade_actuator_count += 1;
+        }
+
+        if sell_scheme.take_profit_ladder.is_some() {
+            trade_actuator_count += 1;
+        }
+
         if trade_actuator_count.cmp(&1) == Ordering::Greater {
-            paniq!("config file error in {}:\n\nonly either one of 'trade_at_price|hold_trade_below_price' or 'trade_at_profit_perc|hold_trade_below_profit_perc' combination is allowed in the Sell function (P00013a)", trade_scheme)
+            paniq!("config file error in {}:\n\nonly either one of 'trade_at_price|hold_trade_below_price', 'trade_at_profit_perc|hold_trade_below_profit_perc' or 'trailing_stop_loss_perc|stop_loss_floor_price' combination or 'take_profit_ladder' is allowed in the Sell function (P00013a)", trade_scheme)
         }
         // </--- check and confirm that only either one of 'trade_at_price|hold_trade_below_price' or 'trade_at_profit_perc|hold_trade_below_profit_perc' combination is used in the trade actuator --->
 
@@ -320,6 +1018,10 @@ impl TradeValidation {
             if let Some(TradeActuatorForSell::Percentage(_)) = a {
                 paniq!("config file error in {}:\n\neither of 'trade_at_profit_perc' and 'hold_trade_below_profit_perc' are only allowed in the BuySell function (P00014a)", trade_scheme)
             };
+
+            if sell_scheme.is_take_profit_ladder_perc_used() {
+                paniq!("config file error in {}:\n\n'trade_at_profit_perc' in the 'take_profit_ladder' is only allowed in the BuySell function (P00014c)", trade_scheme)
+            }
         }
         // </--- check and confirm that either of 'trade_at_profit_perc' and 'hold_trade_below_profit_perc' are ONLY used in the [TradeType::BuySell] --->
 
@@ -341,6 +1043,43 @@ impl TradeValidation {
         Ok(())
     }
 
+    // check the token transfer scheme and resolve the [wallet_name] against the secrets accounts
+    pub fn sanity_check_token_transfer_scheme(
+        s: &TokenTransfer,
+        accounts: &[Accounts],
+    ) -> anyhow::Result<()> {
+        // [gas_price] and [gas_limit]
+        if s.gas_price.is_some() && s.gas_limit.is_none() {
+            paniq!("config file error in {:?}:\n\n'gas_limit' is mandatory when 'gas_price' is active (P00021a)", s)
+        }
+
+        let recipient_wallet_address_h160 = s.recipient_wallet_address_h160()?;
+
+        let account = accounts.iter().find(|a| a.account.name == s.wallet_name);
+
+        match account {
+            Some(a) => {
+                log::debug!(
+                    "found the '{}' wallet in the secrets accounts",
+                    s.wallet_name
+                );
+
+                if a.account.address_h160()? != recipient_wallet_address_h160 {
+                    paniq!("config file error in {:?}:\n\n'recipient_wallet_address' does not match the address of the '{}' account in the secrets file (P00021b)", s, s.wallet_name)
+                }
+            }
+            None => {
+                log::warn!(
+                    "'{}' wallet was not found in the secrets accounts, the tokens will be transferred to {:?}",
+                    s.wallet_name,
+                    recipient_wallet_address_h160
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn provider_validation(provider: &ProviderEntity) -> anyhow::Result<()> {
         let mut basic_auth_fields_count = 0;
 
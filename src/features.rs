@@ -3,3 +3,13 @@ pub mod sell;
 pub mod trade;
 pub mod trade_helper;
 pub mod order_book_helper;
+pub mod transfer;
+pub mod mempool_helper;
+pub mod honeypot_helper;
+pub mod tax_helper;
+pub mod take_profit_ladder_helper;
+pub mod price_impact_helper;
+pub mod rug_pull_helper;
+pub mod risk_scanner_helper;
+pub mod fill_helper;
+pub mod cancel_tx;
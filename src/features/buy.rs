@@ -1,39 +1,50 @@
 use crate::common::constants::default_values::DefaultValues;
 use crate::common::errors::{OrderBookError, TradingError};
 use crate::common::helpers::date::get_elapsed_time_ms;
-use crate::common::helpers::timer::{interruptable_sleep, tokio_sleep};
+use crate::common::helpers::timer::tokio_sleep;
 use crate::common::helpers::urls::get_tx_hash_url;
 use crate::common::models::trade_scheme::{BuyScheme, TradeSchemeVariant};
+use crate::common::utils::ether::{gwei_to_human_display, units_to_human_display};
+use crate::common::utils::f256::ToF256Units;
 use crate::controllers::cli::entry_points::EntryPoints;
+use crate::controllers::contracts::QuantContracts;
 use crate::controllers::quant::quant::{Quant, TradeContext};
-use crate::features::order_book_helper::{OrderBookHelper, OrderBookR};
+use crate::features::fill_helper::FillHelper;
+use crate::features::honeypot_helper::HoneypotHelper;
+use crate::features::mempool_helper::MempoolHelper;
+use crate::features::order_book_helper::{BuyEntryR, OrderBookHelper, OrderBookR};
+use crate::features::price_impact_helper::PriceImpactHelper;
+use crate::features::risk_scanner_helper::RiskScannerHelper;
+use crate::features::tax_helper::{TaxHelper, TaxR};
 use crate::features::trade_helper::{
-    AmountInCtx, AmountOutCtx, GasCtx, QuantTradeHelper, TokenPairR,
+    AmountInCtx, AmountOutCtx, GasCtx, GasTxCtx, PendingSwapCtx, QuantTradeHelper, TokenPairR,
 };
-use ethers::middleware::SignerMiddleware;
-use ethers::prelude::builders::ContractCall;
-use ethers::providers::Provider;
-use ethers::signers::LocalWallet;
 use ethers::types::{TransactionReceipt, U256};
 use min_max::max;
 use std::time::Instant;
 
 pub struct QuantBuy {
-    transaction_receipt: Option<TransactionReceipt>,
+    pub transaction_receipt: Option<TransactionReceipt>,
+    pub buy_entry: Option<BuyEntryR>,
 }
 
 impl QuantBuy {
     fn amount_token_in(ctx: &TradeContext<BuyScheme>) -> anyhow::Result<AmountInCtx> {
         let amount_of_token_in = &ctx.scheme.amount_of_token_in.to_owned();
-        let amount_of_token_in_u256 = ctx.scheme.amount_of_token_in_u256()?;
+        let amount_of_token_in_u256 = ctx
+            .scheme
+            .amount_of_token_in_u256(ctx.contracts.token_in_decimals)?;
 
         let a = AmountInCtx {
             amount_of_token_in: amount_of_token_in.to_string(),
             amount_of_token_in_u256,
             slippage: ctx.scheme.slippage as u64,
+            perc_increase_slippage: ctx.scheme.perc_increase_slippage,
+            max_slippage: ctx.scheme.max_slippage,
             token_in_contract: ctx.feed.token_in_contract.to_owned(),
             token_in_h160: ctx.feed.token_in_h160,
             token_in_symbol: ctx.scheme.token_in_symbol.to_owned(),
+            token_in_decimals: ctx.contracts.token_in_decimals,
         };
 
         Ok(a)
@@ -43,21 +54,36 @@ impl QuantBuy {
         ctx: &TradeContext<BuyScheme>,
         amount_of_token_in_u256: U256,
     ) -> anyhow::Result<AmountOutCtx> {
-        let amount_out_list_contract_call: ContractCall<
-            SignerMiddleware<Provider<_>, LocalWallet>,
-            Vec<U256>,
-        > = ctx.contracts.router.get_amounts_out(
-            amount_of_token_in_u256,
-            vec![ctx.feed.token_in_h160, ctx.feed.token_out_h160],
-        );
+        let (path, amount_out_list) =
+            QuantTradeHelper::amount_out_list(ctx, amount_of_token_in_u256).await?;
+
+        let a = AmountOutCtx {
+            path,
+            amount_out_list,
+            token_out_contract: ctx.feed.token_out_contract.to_owned(),
+            token_out_symbol: ctx.scheme.token_out_symbol.to_owned(),
+            token_out_h160: ctx.feed.token_out_h160,
+            token_out_decimals: ctx.contracts.token_out_decimals,
+        };
 
-        let amount_out_list = amount_out_list_contract_call.call().await?;
+        Ok(a)
+    }
+
+    // the amount of token in required to buy exactly [amount_of_token_out_u256] tokens
+    async fn amount_token_in_for_exact_output(
+        ctx: &TradeContext<BuyScheme>,
+        amount_of_token_out_u256: U256,
+    ) -> anyhow::Result<AmountOutCtx> {
+        let (path, amount_out_list) =
+            QuantTradeHelper::amount_in_list(ctx, amount_of_token_out_u256).await?;
 
         let a = AmountOutCtx {
+            path,
             amount_out_list,
             token_out_contract: ctx.feed.token_out_contract.to_owned(),
             token_out_symbol: ctx.scheme.token_out_symbol.to_owned(),
             token_out_h160: ctx.feed.token_out_h160,
+            token_out_decimals: ctx.contracts.token_out_decimals,
         };
 
         Ok(a)
@@ -67,8 +93,9 @@ impl QuantBuy {
         let g = GasCtx {
             gas_price: ctx.scheme.gas_price.to_owned(),
             gas_price_gwei: ctx.scheme.gas_price_gwei()?,
-            gas_limit: ctx.scheme.gas_limit,
-            gas_limit_u256: ctx.scheme.gas_limit_u256()?,
+            gas_price_multiplier_perc: ctx.scheme.gas_price_multiplier_perc,
+            min_gas_price_gwei: ctx.scheme.min_gas_price_gwei()?,
+            gas_limit_ctx: QuantTradeHelper::gas_limit_ctx(&ctx.scheme)?,
             tx_timeout_in_ms: ctx.scheme.tx_timeout_in_ms,
             max_gas_price: ctx.scheme.max_gas_price.to_owned(),
             max_gas_price_gwei: ctx.scheme.max_gas_price_gwei()?,
@@ -83,6 +110,7 @@ impl QuantBuy {
         q: &Quant,
         entry_points: &EntryPoints,
         token_pair: &TokenPairR,
+        pending_swap: &mut Option<PendingSwapCtx>,
     ) -> anyhow::Result<OrderBookR> {
         log::debug!("initializing the order book...");
 
@@ -123,12 +151,13 @@ impl QuantBuy {
                 //////////////////////////////
                 // todo: this is a temporary hack to interrupt the the 'First Buy Attempt'
                 //  remove this while converting this whole thing into a micro service
-                interruptable_sleep(
-                    DefaultValues::BUY_INTERRUPTER_KEYWORD.parse()?,
-                    ctx.scheme.name.clone(),
-                    sleep_for_ms,
-                )
-                .await?;
+                q.interrupter
+                    .interruptable_sleep(
+                        DefaultValues::BUY_INTERRUPTER_KEYWORD,
+                        ctx.scheme.name.clone(),
+                        sleep_for_ms,
+                    )
+                    .await;
                 //////////////////////////////
                 //////////////////////////////
                 //////////////////////////////
@@ -139,6 +168,13 @@ impl QuantBuy {
             }
         }
 
+        let trade_actuator = ctx.scheme.trade_actuator()?;
+        let amount_of_token_out_u256 = ctx
+            .scheme
+            .amount_of_token_out_u256(ctx.contracts.token_out_decimals)?;
+
+        let mut tax_r: Option<TaxR> = None;
+
         let mut trade_attempt_count = 1_u64;
         let first_trade_attempt_instant = Instant::now();
 
@@ -147,18 +183,136 @@ impl QuantBuy {
             let current_trade_attempt_instant = Instant::now();
 
             // token amount out handler
-            let amount_out_ctx =
-                Self::amount_token_out(ctx, token_in_ctx.amount_of_token_in_u256).await?;
-            let token_out_ctx = QuantTradeHelper::token_out(&amount_in_ctx, &amount_out_ctx)?;
+            let mut token_out_ctx = match amount_of_token_out_u256 {
+                None => {
+                    let amount_out_ctx =
+                        Self::amount_token_out(ctx, token_in_ctx.amount_of_token_in_u256).await?;
+
+                    QuantTradeHelper::token_out(
+                        &amount_in_ctx,
+                        &amount_out_ctx,
+                        trade_attempt_count,
+                    )?
+                }
+                Some(amount_out) => {
+                    let amount_out_ctx =
+                        Self::amount_token_in_for_exact_output(ctx, amount_out).await?;
+
+                    QuantTradeHelper::token_out_for_exact_output(
+                        &amount_in_ctx,
+                        &amount_out_ctx,
+                        trade_attempt_count,
+                    )?
+                }
+            };
+
+            // the price impact of the swap is computed from the reserves of the pairs along the path
+            let is_price_impact_acceptable = match ctx.scheme.max_price_impact_perc {
+                None => true,
+                Some(m) => {
+                    PriceImpactHelper::is_price_impact_acceptable(
+                        ctx,
+                        q,
+                        &token_in_ctx,
+                        &token_out_ctx,
+                        m,
+                    )
+                    .await?
+                }
+            };
+
+            if !entry_points.dry_run
+                && !is_price_impact_acceptable
+                && ctx.scheme.is_abort_on_max_price_impact_active()
+            {
+                log::debug!("terminating the Buy trade...");
+
+                return Err(OrderBookError::Buy(
+                    "the price impact of the swap is above 'max_price_impact_perc'",
+                )
+                .into());
+            }
+
+            // hold the trade until the live price meets the trade actuator
+            // an exact output Buy is also held while it costs more than [amount_of_token_in]
+            // the trade is also held while the price impact is above [max_price_impact_perc]
+            if !entry_points.dry_run
+                && (!OrderBookHelper::is_buy_trade_actuated(
+                    &trade_actuator,
+                    &token_out_ctx.price_of_token_out_per_token_in_human_readable_format,
+                ) || !OrderBookHelper::is_buy_exact_output_affordable(
+                    &token_in_ctx,
+                    &token_out_ctx,
+                ) || !is_price_impact_acceptable)
+            {
+                // if the [max_allowed_time_for_trading_in_ms] is available then check whether the max allowed trading time has reached
+                if let Some(max_allowed_time_for_trading_in_ms_ok) =
+                    max_allowed_time_for_trading_in_ms
+                {
+                    let time_elapsed_since_token_pairing_ms =
+                        OrderBookHelper::time_since_token_paired(&token_paired_time);
+
+                    if time_elapsed_since_token_pairing_ms >= max_allowed_time_for_trading_in_ms_ok
+                    {
+                        log::debug!("terminating the Buy trade...");
+
+                        return Err(OrderBookError::Buy(
+                            "exhausted the max allowed time while holding the Buy trade for the trade actuator",
+                        )
+                        .into());
+                    }
+                }
+
+                tokio_sleep(DefaultValues::TRADE_ACTUATOR_POLLING_INTERVAL_IN_MS).await;
+
+                continue;
+            }
 
             // gas price handler
             let gas_ctx = Self::gas(ctx)?;
-            let gas_tx_ctx = QuantTradeHelper::gas(&gas_ctx, trade_attempt_count)?;
+            let mut gas_tx_ctx = QuantTradeHelper::gas(q, &gas_ctx, trade_attempt_count).await?;
+
+            // the transfer tax is detected once, before the first swap is sent
+            if tax_r.is_none() && ctx.scheme.is_auto_tax_detection_active() {
+                tax_r = Some(
+                    TaxHelper::tax(
+                        ctx,
+                        q,
+                        &token_in_ctx,
+                        &token_out_ctx,
+                        &gas_tx_ctx,
+                        ctx.scheme.is_token_out_deflationary,
+                    )
+                    .await,
+                );
+            }
+
+            let is_deflationary_token = match &tax_r {
+                None => ctx.scheme.is_token_out_deflationary,
+                Some(t) => {
+                    TaxHelper::adjust_amount_out_min(&mut token_out_ctx, t);
+
+                    t.is_deflationary_token
+                }
+            };
 
             log::debug!("\n");
             QuantTradeHelper::print_info(&token_in_ctx, &token_out_ctx, &gas_tx_ctx, q);
             log::debug!("\n\n\n");
 
+            // simulate a Buy and an immediate Sell before the first swap is sent
+            if trade_attempt_count == 1 && ctx.scheme.is_honeypot_check_active() {
+                HoneypotHelper::check(
+                    ctx,
+                    q,
+                    &token_in_ctx,
+                    &token_out_ctx,
+                    &gas_tx_ctx,
+                    ctx.scheme.honeypot_max_loss_perc(),
+                )
+                .await?;
+            }
+
             if entry_points.dry_run {
                 log::info!("dry run successfull...");
                 log::debug!("\n\n\n");
@@ -167,6 +321,8 @@ impl QuantBuy {
                     transaction_receipt: None,
                     token_in_ctx,
                     amount_in_ctx,
+                    token_out_ctx,
+                    fill: None,
                 };
 
                 return Ok(order_r);
@@ -176,9 +332,10 @@ impl QuantBuy {
                 ctx,
                 &token_in_ctx,
                 &token_out_ctx,
-                &gas_tx_ctx,
-                &q,
-                ctx.scheme.is_token_out_deflationary,
+                &mut gas_tx_ctx,
+                q,
+                is_deflationary_token,
+                pending_swap,
             )
             .await;
 
@@ -212,10 +369,20 @@ impl QuantBuy {
 
                     log::debug!("finishing up the Buy trade");
 
+                    let fill = FillHelper::fill(
+                        q,
+                        &token_in_ctx,
+                        &token_out_ctx,
+                        &gas_tx_ctx,
+                        &swap_tx_receipt,
+                    );
+
                     let order_r = OrderBookR {
-                        transaction_receipt: None,
+                        transaction_receipt: Some(swap_tx_receipt),
                         token_in_ctx,
                         amount_in_ctx,
+                        token_out_ctx,
+                        fill,
                     };
 
                     return Ok(order_r);
@@ -261,6 +428,8 @@ impl QuantBuy {
                     log::debug!("retry attempt to buy has ended at #{}", trade_attempt_count);
                     log::debug!("terminating the Buy trade...");
 
+                    QuantTradeHelper::warn_pending_swap(pending_swap);
+
                     return Err(OrderBookError::Buy(
                         "exhausted the retry attempts to carry out the Buy trade",
                     )
@@ -281,6 +450,8 @@ impl QuantBuy {
                     OrderBookHelper::time_since_token_paired(&token_paired_time);
 
                 if time_elapsed_since_token_pairing_ms >= max_allowed_time_for_trading_in_ms_ok {
+                    QuantTradeHelper::warn_pending_swap(pending_swap);
+
                     return Err(OrderBookError::Buy(
                         "exhausted the max allowed time to carry out the Buy trade",
                     )
@@ -300,6 +471,285 @@ impl QuantBuy {
         }
     }
 
+    // match the gas price of the pending liquidity tx so that the Buy lands right after it in the same block
+    // a higher gas price would place the Buy ahead of the liquidity add and the swap would fail
+    fn match_gas_price(
+        gas_ctx: &GasCtx,
+        gas_tx_ctx: &mut GasTxCtx,
+        liquidity_gas_price_gwei: U256,
+    ) {
+        let mut gas_price_gwei = liquidity_gas_price_gwei;
+
+        if let Some(max_gas_price_gwei) = gas_ctx.max_gas_price_gwei {
+            if gas_price_gwei > max_gas_price_gwei {
+                log::warn!(
+                    "the gas price of the liquidity tx ({} gwei) has crossed the max allowed gas price, will continue with the max allowed gas price...",
+                    gwei_to_human_display(gas_price_gwei)
+                );
+
+                gas_price_gwei = max_gas_price_gwei;
+            }
+        }
+
+        gas_tx_ctx.gas_price_gwei = gas_price_gwei;
+        gas_tx_ctx.gas_price = gwei_to_human_display(gas_price_gwei).to_string();
+
+        log::debug!(
+            "matched the gas price of the liquidity tx: {} gwei",
+            gas_tx_ctx.gas_price
+        );
+    }
+
+    // snipe the liquidity add from the mempool and buy in the same block
+    // returns [None] if the Buy has to fall back to the regular order book
+    // the snipe tx is replaced by the regular order book if it wasn't mined in time
+    async fn mempool_order_book(
+        ctx: &TradeContext<BuyScheme>,
+        q: &Quant,
+        entry_points: &EntryPoints,
+        pending_swap: &mut Option<PendingSwapCtx>,
+    ) -> anyhow::Result<Option<OrderBookR>> {
+        log::debug!("initializing the mempool order book...");
+
+        // the amount out is computed from the pending liquidity of the direct pair only
+        // [path] and [auto_route] are rejected along with [snipe_liquidity_from_mempool] by the config check
+        let direct_route = vec![ctx.feed.token_in_h160, ctx.feed.token_out_h160];
+
+        if ctx.routes != [direct_route.to_owned()] {
+            log::warn!("the mempool snipe supports only the direct route, falling back to the regular Buy...");
+
+            return Ok(None);
+        }
+
+        if QuantTradeHelper::get_pair_address(ctx, ctx.feed.token_in_h160, ctx.feed.token_out_h160)
+            .await
+            .is_some()
+        {
+            log::warn!("the token pair already exists, falling back to the regular Buy...");
+
+            return Ok(None);
+        }
+
+        let pending_liquidity = match MempoolHelper::wait_for_pending_liquidity(ctx, q).await {
+            Ok(Some(d)) => d,
+            Ok(None) => {
+                log::warn!("the pending transactions subscription dropped, falling back to the regular Buy...");
+
+                return Ok(None);
+            }
+            Err(e) => {
+                log::error!("{:?}", e);
+                log::warn!("the pending transactions subscription failed, falling back to the regular Buy...");
+
+                return Ok(None);
+            }
+        };
+
+        if let Some(min_liquidity_required_u256) = ctx
+            .scheme
+            .min_liquidity_required_u256(ctx.contracts.token_in_decimals)?
+        {
+            if pending_liquidity.amount_of_token_in_u256 < min_liquidity_required_u256 {
+                log::warn!(
+                    "the pending liquidity ({}) is below the min required liquidity, falling back to the regular Buy...",
+                    units_to_human_display(
+                        pending_liquidity.amount_of_token_in_u256,
+                        ctx.contracts.token_in_decimals
+                    )
+                );
+
+                return Ok(None);
+            }
+        }
+
+        // create token in amount context
+        let amount_in_ctx = Self::amount_token_in(ctx)?;
+
+        // create token in context
+        let token_in_ctx = QuantTradeHelper::token_in(&amount_in_ctx)?;
+
+        // the pair has no reserves yet so the amount out is computed from the pending liquidity
+        let amount_out_u256 = QuantTradeHelper::amount_out_from_reserves(
+            token_in_ctx.amount_of_token_in_u256,
+            pending_liquidity.amount_of_token_in_u256,
+            pending_liquidity.amount_of_token_out_u256,
+        );
+
+        let amount_out_ctx = AmountOutCtx {
+            path: direct_route,
+            amount_out_list: vec![token_in_ctx.amount_of_token_in_u256, amount_out_u256],
+            token_out_contract: ctx.feed.token_out_contract.to_owned(),
+            token_out_symbol: ctx.scheme.token_out_symbol.to_owned(),
+            token_out_h160: ctx.feed.token_out_h160,
+            token_out_decimals: ctx.contracts.token_out_decimals,
+        };
+        let token_out_ctx = QuantTradeHelper::token_out(&amount_in_ctx, &amount_out_ctx, 1)?;
+
+        if !entry_points.dry_run
+            && !OrderBookHelper::is_buy_trade_actuated(
+                &ctx.scheme.trade_actuator()?,
+                &token_out_ctx.price_of_token_out_per_token_in_human_readable_format,
+            )
+        {
+            log::warn!("the trade actuator wasn't met at the pending liquidity price, falling back to the regular Buy...");
+
+            return Ok(None);
+        }
+
+        // the pair has no reserves yet so the price impact is computed from the pending liquidity
+        if let Some(max_price_impact_perc) = ctx.scheme.max_price_impact_perc {
+            let price_impact_perc = PriceImpactHelper::price_impact_from_reserves(
+                token_in_ctx.amount_of_token_in_u256,
+                pending_liquidity.amount_of_token_in_u256,
+            );
+
+            log::info!("Price Impact: {:.4}%", price_impact_perc);
+
+            if price_impact_perc > ToF256Units::to_f256(&max_price_impact_perc) {
+                log::warn!(
+                    "[max_price_impact_perc] the price impact of the swap ({:.4}%) is above {}%, falling back to the regular Buy...",
+                    price_impact_perc,
+                    max_price_impact_perc
+                );
+
+                return Ok(None);
+            }
+        }
+
+        // the pair has no liquidity to simulate the round trip swap against before the liquidity add is mined
+        if ctx.scheme.is_honeypot_check_active() {
+            log::warn!("[honeypot_check] the honeypot check cannot be carried out on a pending liquidity, skipping...");
+        }
+
+        if ctx.scheme.is_auto_tax_detection_active() {
+            log::warn!(
+                "[auto_detect_tax] the transfer tax cannot be detected on a pending liquidity, falling back to 'is_token_out_deflationary': {}",
+                ctx.scheme.is_token_out_deflationary
+            );
+        }
+
+        // gas price handler
+        let gas_ctx = Self::gas(ctx)?;
+        let mut gas_tx_ctx = QuantTradeHelper::gas(q, &gas_ctx, 1).await?;
+
+        Self::match_gas_price(&gas_ctx, &mut gas_tx_ctx, pending_liquidity.gas_price_gwei);
+
+        log::debug!("\n");
+        QuantTradeHelper::print_info(&token_in_ctx, &token_out_ctx, &gas_tx_ctx, q);
+        log::debug!("\n\n\n");
+
+        if entry_points.dry_run {
+            log::info!("dry run successfull...");
+            log::debug!("\n\n\n");
+
+            let order_r = OrderBookR {
+                transaction_receipt: None,
+                token_in_ctx,
+                amount_in_ctx,
+                token_out_ctx,
+                fill: None,
+            };
+
+            return Ok(Some(order_r));
+        }
+
+        let swap_result = QuantTradeHelper::swap_tokens(
+            ctx,
+            &token_in_ctx,
+            &token_out_ctx,
+            &mut gas_tx_ctx,
+            q,
+            ctx.scheme.is_token_out_deflationary,
+            pending_swap,
+        )
+        .await;
+
+        match swap_result {
+            Ok(swap_tx_receipt) => {
+                log::info!("YAY!!! The Buy trade was sniped from the mempool!");
+                log::info!(
+                    "tx hash ({:?}) {}",
+                    swap_tx_receipt.transaction_hash,
+                    get_tx_hash_url(
+                        swap_tx_receipt.transaction_hash,
+                        q.variables.network_name.clone()
+                    )
+                );
+
+                let fill = FillHelper::fill(
+                    q,
+                    &token_in_ctx,
+                    &token_out_ctx,
+                    &gas_tx_ctx,
+                    &swap_tx_receipt,
+                );
+
+                let order_r = OrderBookR {
+                    transaction_receipt: Some(swap_tx_receipt),
+                    token_in_ctx,
+                    amount_in_ctx,
+                    token_out_ctx,
+                    fill,
+                };
+
+                Ok(Some(order_r))
+            }
+            Err(swap_result_err) => {
+                if let Some(TradingError::SwapToken(_)) =
+                    swap_result_err.downcast_ref::<TradingError>()
+                {
+                    log::error!(
+                        "the swap_tokens method returned a 'SwapToken' error: {:?}",
+                        swap_result_err
+                    );
+                    log::warn!("the mempool snipe failed, falling back to the regular Buy...");
+
+                    return Ok(None);
+                }
+
+                log::error!(
+                    "the swap_tokens method returned an unknown error : {:?}",
+                    swap_result_err
+                );
+                log::debug!("terminating the Buy trade...");
+
+                Err(swap_result_err)
+            }
+        }
+    }
+
+    // the effective entry of the Buy trade is its actual fill
+    fn buy_entry(ctx: &TradeContext<BuyScheme>, order_r: &OrderBookR) -> Option<BuyEntryR> {
+        let f = match &order_r.fill {
+            None => {
+                if order_r.transaction_receipt.is_some() {
+                    log::warn!(
+                        "unable to find the amount of {} received in the Buy tx receipt",
+                        ctx.scheme.token_out_symbol
+                    );
+                }
+
+                return None;
+            }
+            Some(d) => d,
+        };
+
+        log::info!(
+            "Buy entry price: {:.14} {} per {}",
+            f.effective_price_human_readable_format,
+            ctx.scheme.token_in_symbol,
+            ctx.scheme.token_out_symbol
+        );
+
+        let b = BuyEntryR {
+            amount_of_token_in: f.amount_of_token_in_sent,
+            amount_of_token_out_received: f.amount_of_token_out_received,
+            entry_price_human_readable_format: f.effective_price_human_readable_format,
+        };
+
+        Some(b)
+    }
+
     pub async fn new(
         ctx: &TradeContext<BuyScheme>,
         q: &Quant,
@@ -307,16 +757,76 @@ impl QuantBuy {
     ) -> anyhow::Result<Self> {
         log::debug!("initializing the 'Buy' function...");
 
+        // the contract of [token_out] is scanned before anything is sent to the network
+        if ctx.scheme.is_risk_scan_active() {
+            let token_out_erc20 =
+                QuantContracts::ecr20_contract(&q.middleware.client, ctx.feed.token_out_h160);
+
+            RiskScannerHelper::check(
+                q,
+                &token_out_erc20,
+                &ctx.scheme.token_out_symbol,
+                ctx.scheme.max_risk_score,
+            )
+            .await?;
+        }
+
+        // the swap tx of the mempool snipe is carried into the regular order book
+        let mut pending_swap: Option<PendingSwapCtx> = None;
+
+        if ctx.scheme.is_mempool_sniping_active() {
+            // the token has to be approved before the liquidity add shows up in the mempool
+            let _ = QuantTradeHelper::approve(
+                &ctx.contracts,
+                q,
+                ctx.scheme.approve_gas_price_gwei()?,
+                QuantTradeHelper::approve_gas_limit_ctx(&ctx.scheme)?,
+                ctx.scheme.token_in_symbol.to_owned(),
+            )
+            .await?;
+
+            if let Some(o) =
+                Self::mempool_order_book(ctx, q, entry_points, &mut pending_swap).await?
+            {
+                let buy_entry = Self::buy_entry(ctx, &o);
+
+                let q_buy = QuantBuy {
+                    transaction_receipt: o.transaction_receipt,
+                    buy_entry,
+                };
+
+                return Ok(q_buy);
+            }
+        }
+
+        // the liquidity of [token_out] is added against the last but one token of the route
+        let pair_route = QuantTradeHelper::pair_route(ctx).await?;
+        let paired_token_h160 = pair_route[pair_route.len() - 2];
+        let paired_token_erc20 =
+            QuantContracts::ecr20_contract(&q.middleware.client, paired_token_h160);
+
+        // [min_liquidity_required] is measured in the paired token
+        let paired_token_decimals = if paired_token_h160 == ctx.feed.token_in_h160 {
+            ctx.contracts.token_in_decimals
+        } else {
+            QuantContracts::erc20_decimals(&paired_token_erc20).await?
+        };
+        let min_liquidity_required_u256 = ctx
+            .scheme
+            .min_liquidity_required_u256(paired_token_decimals)?;
+
         // create token pair address
         // the method will wait until the token pair address is created if not found
-        let pair_address = QuantTradeHelper::create_pair(ctx).await?;
+        let pair_address =
+            QuantTradeHelper::create_pair(ctx, paired_token_h160, ctx.feed.token_out_h160).await?;
 
         // create token pair value
         // the method will wait until the minimum required liquidity is found
         let token_pair = QuantTradeHelper::token_pair_value(
-            &ctx.contracts,
+            &paired_token_erc20,
+            paired_token_decimals,
             pair_address,
-            &ctx.scheme.min_liquidity_required_u256()?,
+            &min_liquidity_required_u256,
         )
         .await?;
 
@@ -325,16 +835,19 @@ impl QuantBuy {
             &ctx.contracts,
             q,
             ctx.scheme.approve_gas_price_gwei()?,
-            ctx.scheme.approve_gas_limit_u256()?,
+            QuantTradeHelper::approve_gas_limit_ctx(&ctx.scheme)?,
             ctx.scheme.token_in_symbol.to_owned(),
         )
         .await?;
 
         // create the order buy
-        let o = Self::order_book(ctx, q, entry_points, &token_pair).await?;
+        let o = Self::order_book(ctx, q, entry_points, &token_pair, &mut pending_swap).await?;
+
+        let buy_entry = Self::buy_entry(ctx, &o);
 
         let q_buy = QuantBuy {
             transaction_receipt: o.transaction_receipt,
+            buy_entry,
         };
 
         Ok(q_buy)
@@ -0,0 +1,251 @@
+use crate::common::constants::default_values::DefaultValues;
+use crate::common::errors::CancelTxError;
+use crate::common::helpers::ethers::get_network_gas_price;
+use crate::common::helpers::urls::get_tx_hash_url;
+use crate::common::utils::ether::{decimals_to_gwei, gwei_to_human_display};
+use crate::common::utils::u256::percentage_of_u256;
+use crate::controllers::cli::cancel_tx_inputs::CancelTxInputs;
+use crate::controllers::cli::entry_points::EntryPoints;
+use crate::controllers::quant::quant::Quant;
+use ethers::core::types::{TransactionReceipt, TransactionRequest};
+use ethers::prelude::Middleware;
+use ethers::types::U256;
+use min_max::max;
+use std::ops::Add;
+
+pub struct QuantCancelTx;
+
+impl QuantCancelTx {
+    // the gas price of the pending tx with [nonce] is read from the txpool of the node
+    // not every node exposes its txpool, the gas price is unknown then
+    async fn pending_tx_gas_price(q: &Quant, nonce: U256) -> Option<U256> {
+        let txpool_content = match q.middleware.client.txpool_content().await {
+            Ok(d) => d,
+            Err(e) => {
+                log::debug!("unable to fetch the txpool content: {:?}", e);
+                log::warn!("the node doesn't expose its txpool, the gas price of the pending tx is unknown");
+
+                return None;
+            }
+        };
+
+        let account_h160 = q.variables.account_address_h160;
+
+        // a tx waiting behind a nonce gap is in the queued txs
+        txpool_content
+            .pending
+            .get(&account_h160)
+            .into_iter()
+            .chain(txpool_content.queued.get(&account_h160))
+            .flat_map(|d| d.values())
+            .find(|d| d.nonce == nonce)
+            .map(|d| d.gas_price)
+    }
+
+    // the replacement is accepted by the nodes only if it pays at least [REPLACEMENT_TX_MIN_PERC_INCREASE_GAS_PRICE] more than the pending tx
+    // the higher of the two is increased by [CANCEL_TX_PERC_INCREASE_GAS_PRICE]
+    fn replacement_gas_price(
+        network_gas_price_gwei: U256,
+        pending_tx_gas_price_gwei: Option<U256>,
+    ) -> U256 {
+        let min_gas_price_gwei = match pending_tx_gas_price_gwei {
+            None => network_gas_price_gwei,
+            Some(p) => max!(
+                network_gas_price_gwei,
+                p.add(percentage_of_u256(
+                    p,
+                    DefaultValues::REPLACEMENT_TX_MIN_PERC_INCREASE_GAS_PRICE,
+                ))
+            ),
+        };
+
+        min_gas_price_gwei.add(percentage_of_u256(
+            min_gas_price_gwei,
+            DefaultValues::CANCEL_TX_PERC_INCREASE_GAS_PRICE,
+        ))
+    }
+
+    async fn default_gas_price(q: &Quant, nonce: U256) -> anyhow::Result<String> {
+        let network_gas_price_gwei = get_network_gas_price(&q.middleware.client).await?;
+
+        log::debug!("fetching the gas price of the pending tx...");
+        let pending_tx_gas_price_gwei = Self::pending_tx_gas_price(q, nonce).await;
+
+        if let Some(p) = pending_tx_gas_price_gwei {
+            log::info!(
+                "gas price of the pending tx (GWEI): {}",
+                gwei_to_human_display(p)
+            );
+        }
+
+        let gas_price_gwei =
+            Self::replacement_gas_price(network_gas_price_gwei, pending_tx_gas_price_gwei);
+
+        Ok(gwei_to_human_display(gas_price_gwei).to_string())
+    }
+
+    fn print_info(q: &Quant, nonce: U256, gas_price: &str) {
+        log::debug!("Cancel tx details:");
+
+        log::info!("Account address: {}", q.variables.account_address);
+        log::info!("Nonce: {}", nonce);
+        log::info!("Gas Price (GWEI): {}", gas_price);
+        log::info!("Gas Limit: {}", DefaultValues::NATIVE_TRANSFER_GAS_LIMIT);
+    }
+
+    // a 0-value transfer to the account itself replaces the pending tx with the same nonce
+    async fn cancel_tx(
+        q: &Quant,
+        nonce: U256,
+        gas_price_gwei: U256,
+    ) -> anyhow::Result<TransactionReceipt> {
+        log::debug!("initializing the cancel tx...");
+
+        let cancel_tx = TransactionRequest::new()
+            .to(q.variables.account_address_h160)
+            .value(U256::zero())
+            .gas(DefaultValues::NATIVE_TRANSFER_GAS_LIMIT)
+            .gas_price(gas_price_gwei)
+            .nonce(nonce);
+
+        log::debug!("attempting to send the cancel transaction...");
+
+        let pending_tx = q.middleware.client.send_transaction(cancel_tx, None).await;
+
+        match pending_tx {
+            Ok(t) => {
+                let tx_hash = *t;
+                let tx_url = get_tx_hash_url(tx_hash, q.variables.network_name.clone());
+
+                log::info!("tx hash ({:?}) {}", tx_hash, tx_url);
+                log::debug!("waiting for the tx receipt...");
+
+                let tx_receipt_call = &t.await;
+                match tx_receipt_call {
+                    Ok(tx_receipt) => match tx_receipt {
+                        Some(r) => match r.status {
+                            None => Err(CancelTxError::SendTx(
+                                "the cancel tx status did not return anything",
+                            )
+                            .into()),
+                            Some(s) => {
+                                let status_code = s.as_u32();
+
+                                if status_code == 1 {
+                                    log::debug!("cancel tx receipt received");
+                                    log::debug!("cancel tx receipt: {:?}", r);
+
+                                    Ok(r.clone())
+                                } else {
+                                    Err(CancelTxError::SendTx(
+                                        "the cancel tx status returned failure",
+                                    )
+                                    .into())
+                                }
+                            }
+                        },
+                        None => Err(CancelTxError::SendTx(
+                            "the cancel tx receipt did not return anything",
+                        )
+                        .into()),
+                    },
+                    Err(e) => {
+                        let err_text = "cancel tx receipt returned an error";
+                        log::error!("{:?}", e);
+
+                        Err(CancelTxError::SendTx(err_text).into())
+                    }
+                }
+            }
+            Err(e) => {
+                // the pending tx could have been mined in the meantime
+                let err_text = "some error occured while sending the cancel transaction";
+                log::error!("{:?}", e);
+
+                Err(CancelTxError::SendTx(err_text).into())
+            }
+        }
+    }
+
+    pub async fn new(q: &Quant, entry_points: &EntryPoints) -> anyhow::Result<Self> {
+        log::debug!("initializing the 'Cancel Tx' function...");
+
+        log::debug!("fetching the pending nonces of the account...");
+        let pending_nonces = q.middleware.pending_nonces().await?;
+
+        if pending_nonces.is_empty() {
+            log::info!(
+                "no pending tx was found for the account {}, exiting...",
+                q.variables.account_address
+            );
+
+            return Ok(QuantCancelTx);
+        }
+
+        log::info!(
+            "found {} pending tx(s) for the account {}",
+            pending_nonces.len(),
+            q.variables.account_address
+        );
+
+        let nonce = CancelTxInputs::nonce(&pending_nonces)?;
+        let gas_price = CancelTxInputs::gas_price(Self::default_gas_price(q, nonce).await?)?;
+        let gas_price_gwei = decimals_to_gwei(&gas_price)?;
+
+        log::debug!("\n");
+        Self::print_info(q, nonce, &gas_price);
+        log::debug!("\n\n\n");
+
+        if entry_points.dry_run {
+            log::info!("dry run successfull...");
+
+            return Ok(QuantCancelTx);
+        }
+
+        let r = Self::cancel_tx(q, nonce, gas_price_gwei).await?;
+
+        log::info!(
+            "YAY!!! the pending tx with the nonce {} was cancelled!",
+            nonce
+        );
+        log::info!(
+            "tx hash ({:?}) {}",
+            r.transaction_hash,
+            get_tx_hash_url(r.transaction_hash, q.variables.network_name.clone())
+        );
+
+        Ok(QuantCancelTx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replacement_gas_price_without_a_pending_tx_gas_price() {
+        // the network gas price + 25%
+        assert_eq!(
+            QuantCancelTx::replacement_gas_price(U256::from(100), None),
+            U256::from(125)
+        );
+    }
+
+    #[test]
+    fn replacement_gas_price_over_the_pending_tx_gas_price() {
+        // the pending tx gas price + 10%, then + 25%
+        assert_eq!(
+            QuantCancelTx::replacement_gas_price(U256::from(100), Some(U256::from(200))),
+            U256::from(275)
+        );
+    }
+
+    #[test]
+    fn replacement_gas_price_over_the_network_gas_price() {
+        // the network gas price is above the pending tx gas price + 10%
+        assert_eq!(
+            QuantCancelTx::replacement_gas_price(U256::from(200), Some(U256::from(100))),
+            U256::from(250)
+        );
+    }
+}
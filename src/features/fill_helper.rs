@@ -0,0 +1,144 @@
+use crate::common::utils::ether::{ether_to_human_display, units_to_human_display};
+use crate::common::utils::f256::{divide_into_f256, ToF256Units};
+use crate::controllers::quant::quant::Quant;
+use crate::features::trade_helper::{GasTxCtx, QuantTradeHelper, TokenInCTx, TokenOutCTx};
+use ethers::core::types::TransactionReceipt;
+use ethers::types::U256;
+use qd::Quad;
+use std::ops::{Mul, Sub};
+
+pub struct FillHelper;
+
+/// the actual fill of a mined swap decoded from the ERC20 'Transfer' events of its tx receipt
+#[derive(Debug, Clone)]
+pub struct FillR {
+    pub amount_of_token_in_sent_u256: U256,
+    pub amount_of_token_in_sent: Quad,
+    pub amount_of_token_out_received_u256: U256,
+    pub amount_of_token_out_received: Quad,
+
+    /// the amount of token_in spent for a single token_out
+    pub effective_price_human_readable_format: Quad,
+
+    /// the shortfall of the fill against the quote of the swap.
+    /// it is negative when the fill is better than the quote
+    pub realised_slippage_perc: Quad,
+
+    pub gas_fees_u256: U256,
+    pub gas_fees: Quad,
+}
+
+impl FillHelper {
+    // the quote of an exact input swap is the amount of [token_out], the quote of an exact output swap is the amount of [token_in]
+    // the transfer taxes of a deflationary token are part of the realised slippage
+    fn realised_slippage_perc(
+        token_out_ctx: &TokenOutCTx,
+        amount_of_token_in_sent_u256: U256,
+        amount_of_token_out_received_u256: U256,
+    ) -> Quad {
+        let (quote_u256, shortfall) = match &token_out_ctx.exact_output {
+            None => (
+                token_out_ctx.amount_out_max_u256,
+                ToF256Units::to_f256(&token_out_ctx.amount_out_max_u256)
+                    .sub(ToF256Units::to_f256(&amount_of_token_out_received_u256)),
+            ),
+            Some(e) => (
+                e.amount_in_u256,
+                ToF256Units::to_f256(&amount_of_token_in_sent_u256)
+                    .sub(ToF256Units::to_f256(&e.amount_in_u256)),
+            ),
+        };
+
+        if quote_u256.is_zero() {
+            return ToF256Units::to_f256(&0_u8);
+        }
+
+        divide_into_f256(&shortfall, &quote_u256).mul(ToF256Units::to_f256(&100_u8))
+    }
+
+    // the native coin is wrapped and unwrapped by the router, so it is sent and received by the router instead of the account
+    // the gas fees are the gas used times the gas price, which is the price actually paid by a legacy tx
+    // returns [None] if the transfers of the swap aren't found in the tx receipt
+    pub fn fill(
+        q: &Quant,
+        token_in_ctx: &TokenInCTx,
+        token_out_ctx: &TokenOutCTx,
+        gas_tx_ctx: &GasTxCtx,
+        tx_receipt: &TransactionReceipt,
+    ) -> Option<FillR> {
+        let account_and_router_h160 =
+            [q.variables.account_address_h160, q.variables.router_in_h160];
+
+        let amount_of_token_in_sent_u256 = QuantTradeHelper::tokens_sent_from_receipt(
+            tx_receipt,
+            token_in_ctx.token_in_h160,
+            &account_and_router_h160,
+        );
+        let amount_of_token_out_received_u256 = QuantTradeHelper::tokens_received_from_receipt(
+            tx_receipt,
+            token_out_ctx.token_out_h160,
+            &account_and_router_h160,
+        );
+
+        if amount_of_token_in_sent_u256.is_zero() || amount_of_token_out_received_u256.is_zero() {
+            log::warn!(
+                "unable to find the transfers of {} and {} in the tx receipt",
+                token_in_ctx.token_in_symbol,
+                token_out_ctx.token_out_symbol
+            );
+
+            return None;
+        }
+
+        let amount_of_token_in_sent =
+            units_to_human_display(amount_of_token_in_sent_u256, token_in_ctx.token_in_decimals);
+        let amount_of_token_out_received = units_to_human_display(
+            amount_of_token_out_received_u256,
+            token_out_ctx.token_out_decimals,
+        );
+        let effective_price_human_readable_format =
+            divide_into_f256(&amount_of_token_in_sent, &amount_of_token_out_received);
+        let realised_slippage_perc = Self::realised_slippage_perc(
+            token_out_ctx,
+            amount_of_token_in_sent_u256,
+            amount_of_token_out_received_u256,
+        );
+        let gas_fees_u256 = tx_receipt
+            .gas_used
+            .unwrap_or_default()
+            .mul(gas_tx_ctx.gas_price_gwei);
+        let gas_fees = ether_to_human_display(gas_fees_u256);
+
+        log::info!(
+            "Amount of {} sent: {}",
+            token_in_ctx.token_in_symbol,
+            amount_of_token_in_sent
+        );
+        log::info!(
+            "Amount of {} received: {}",
+            token_out_ctx.token_out_symbol,
+            amount_of_token_out_received
+        );
+        log::info!(
+            "Effective price: {:.14} {} per {}",
+            effective_price_human_readable_format,
+            token_in_ctx.token_in_symbol,
+            token_out_ctx.token_out_symbol
+        );
+        log::info!("Realised slippage: {:.4}%", realised_slippage_perc);
+        log::info!("Gas fees: {} {}", gas_fees, q.variables.native_token_symbol);
+
+        let f = FillR {
+            amount_of_token_in_sent_u256,
+            amount_of_token_in_sent,
+            amount_of_token_out_received_u256,
+            amount_of_token_out_received,
+            effective_price_human_readable_format,
+            realised_slippage_perc,
+            gas_fees_u256,
+            gas_fees,
+        };
+
+        Some(f)
+    }
+}
@@ -0,0 +1,390 @@
+use crate::common::constants::default_values::DefaultValues;
+use crate::common::errors::TradingError;
+use crate::common::models::trade_scheme::TradeSchemeVariant;
+use crate::common::utils::ether::units_to_human_display;
+use crate::common::utils::f256::{divide_into_f256, ToF256Units};
+use crate::controllers::contracts::QuantContracts;
+use crate::controllers::quant::quant::{Quant, TradeContext};
+use crate::features::trade_helper::{
+    AccountOverride, GasTxCtx, QuantTradeHelper, StateOverride, TokenInCTx, TokenOutCTx,
+};
+use ethers::abi::ethereum_types::H256;
+use ethers::abi::Address;
+use ethers::core::types::TransactionRequest;
+use ethers::core::utils::keccak256;
+use ethers::types::U256;
+use std::ops::Mul;
+
+pub struct HoneypotHelper;
+
+impl HoneypotHelper {
+    // [token_out] -> [token_in] back through the pairs of the Buy
+    fn sell_path(buy_path: &[Address]) -> Vec<Address> {
+        buy_path.iter().rev().cloned().collect()
+    }
+
+    // the storage slot of [key] in a solidity mapping declared at [mapping_slot]
+    fn mapping_slot(key: H256, mapping_slot: H256) -> H256 {
+        H256::from(keccak256(
+            [key.as_bytes(), mapping_slot.as_bytes()].concat(),
+        ))
+    }
+
+    // the slot of 'balances[account]' if the mapping is declared at [mapping_slot]
+    fn balance_slot(account_h160: Address, mapping_slot: u64) -> H256 {
+        Self::mapping_slot(
+            H256::from(account_h160),
+            H256::from_low_u64_be(mapping_slot),
+        )
+    }
+
+    // the slot of 'allowances[owner][spender]' if the mapping is declared at [mapping_slot]
+    fn allowance_slot(owner_h160: Address, spender_h160: Address, mapping_slot: u64) -> H256 {
+        Self::mapping_slot(
+            H256::from(spender_h160),
+            Self::balance_slot(owner_h160, mapping_slot),
+        )
+    }
+
+    fn state_override(token_h160: Address, slot: H256, value_u256: U256) -> StateOverride {
+        let mut account_override = AccountOverride::default();
+        account_override
+            .state_diff
+            .insert(slot, H256::from_uint(&value_u256));
+
+        let mut state_override = StateOverride::new();
+        state_override.insert(token_h160, account_override);
+
+        state_override
+    }
+
+    // the token keeps [value_u256] in one of its first storage slots, which is found by overriding every candidate slot
+    // until [getter_tx] returns the value.
+    // the tokens which don't keep the balances in a plain mapping, e.g. the reflection tokens, return [None]
+    async fn find_slot<F>(
+        q: &Quant,
+        token_h160: Address,
+        getter_tx: &TransactionRequest,
+        value_u256: U256,
+        slot_of: F,
+    ) -> Option<H256>
+    where
+        F: Fn(u64) -> H256,
+    {
+        for mapping_slot in 0..DefaultValues::HONEYPOT_CHECK_STORAGE_SLOTS {
+            let slot = slot_of(mapping_slot);
+            let state_override = Self::state_override(token_h160, slot, value_u256);
+
+            match QuantTradeHelper::simulated_call(q, getter_tx, Some(&state_override)).await {
+                Ok(r) if r.as_ref().len() >= 32 => {
+                    if U256::from_big_endian(&r.as_ref()[..32]) == value_u256 {
+                        log::debug!(
+                            "[honeypot_check] found the mapping of the token at the storage slot {}",
+                            mapping_slot
+                        );
+
+                        return Some(slot);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    log::debug!("the simulated call with a state override failed: {:?}", e);
+
+                    return None;
+                }
+            }
+        }
+
+        None
+    }
+
+    // the state of the account holding [amount_u256] of [token_out] approved to the router,
+    // so that the Sell can be simulated before anything is bought
+    async fn holder_state_override(
+        q: &Quant,
+        token_out_h160: Address,
+        amount_u256: U256,
+    ) -> anyhow::Result<StateOverride> {
+        let account_h160 = q.variables.account_address_h160;
+        let router_h160 = q.variables.router_in_h160;
+        let token_out_erc20 = QuantContracts::ecr20_contract(&q.middleware.client, token_out_h160);
+
+        let balance_slot = Self::find_slot(
+            q,
+            token_out_h160,
+            &token_out_erc20.balance_of(account_h160).tx,
+            amount_u256,
+            |d| Self::balance_slot(account_h160, d),
+        )
+        .await;
+        let allowance_slot = Self::find_slot(
+            q,
+            token_out_h160,
+            &token_out_erc20.allowance(account_h160, router_h160).tx,
+            U256::MAX,
+            |d| Self::allowance_slot(account_h160, router_h160, d),
+        )
+        .await;
+
+        let (balance_slot, allowance_slot) = match (balance_slot, allowance_slot) {
+            (Some(b), Some(a)) => (b, a),
+            _ => {
+                return Err(TradingError::Honeypot(
+                    "unable to simulate the Sell, the node should support the state overrides of 'eth_call' and the token should keep its balances in a plain mapping",
+                )
+                .into())
+            }
+        };
+
+        let mut state_override = Self::state_override(token_out_h160, balance_slot, amount_u256);
+        if let Some(d) = state_override.get_mut(&token_out_h160) {
+            d.state_diff
+                .insert(allowance_slot, H256::from_uint(&U256::MAX));
+        }
+
+        Ok(state_override)
+    }
+
+    // simulate the Buy to the account and then a Sell of the received tokens from the account before the real Buy is sent
+    // both are separate calls, a single round trip swap would send [token_out] back into the pair it was bought from
+    // the largest amount of [token_in] received back is searched for to measure the round trip loss,
+    // which includes the router fees, the price impact and the taxes of [token_out]
+    pub async fn check<T>(
+        ctx: &TradeContext<T>,
+        q: &Quant,
+        token_in_ctx: &TokenInCTx,
+        token_out_ctx: &TokenOutCTx,
+        gas_tx_ctx: &GasTxCtx,
+        max_loss_perc: u8,
+    ) -> anyhow::Result<()>
+    where
+        T: TradeSchemeVariant,
+    {
+        log::info!(
+            "[honeypot_check] simulating a Buy and an immediate Sell of {}...",
+            token_out_ctx.token_out_symbol
+        );
+
+        let amount_in_u256 = token_in_ctx.amount_of_token_in_u256;
+        let tx_deadline_u256 = gas_tx_ctx.tx_deadline_u256;
+
+        // the Buy ->>
+        if !QuantTradeHelper::is_simulated_swap_successful(
+            ctx,
+            q,
+            token_in_ctx,
+            &token_out_ctx.path,
+            U256::zero(),
+            tx_deadline_u256,
+            None,
+        )
+        .await
+        {
+            return Err(TradingError::Honeypot(
+                "the simulated Buy reverted, the token could be a honeypot",
+            )
+            .into());
+        }
+
+        let amount_out_received_u256 = QuantTradeHelper::max_simulated_amount_out(
+            ctx,
+            q,
+            token_in_ctx,
+            &token_out_ctx.path,
+            token_out_ctx.amount_out_max_u256,
+            tx_deadline_u256,
+            DefaultValues::HONEYPOT_CHECK_SEARCH_STEPS,
+            None,
+        )
+        .await;
+
+        if amount_out_received_u256.is_zero() {
+            return Err(TradingError::Honeypot(
+                "the simulated Buy didn't receive any token, the token could be a honeypot",
+            )
+            .into());
+        }
+
+        // the Sell ->>
+        let sell_token_in_ctx = TokenInCTx {
+            amount_of_token_in_u256: amount_out_received_u256,
+            amount_of_token_in: units_to_human_display(
+                amount_out_received_u256,
+                token_out_ctx.token_out_decimals,
+            )
+            .to_string(),
+            token_in_contract: token_out_ctx.token_out_contract.to_owned(),
+            token_in_symbol: token_out_ctx.token_out_symbol.to_owned(),
+            token_in_h160: token_out_ctx.token_out_h160,
+            token_in_decimals: token_out_ctx.token_out_decimals,
+        };
+        let sell_path = Self::sell_path(&token_out_ctx.path);
+        let state_override =
+            Self::holder_state_override(q, token_out_ctx.token_out_h160, amount_out_received_u256)
+                .await?;
+
+        log::debug!("sell path: {:?}", sell_path);
+
+        if !QuantTradeHelper::is_simulated_swap_successful(
+            ctx,
+            q,
+            &sell_token_in_ctx,
+            &sell_path,
+            U256::zero(),
+            tx_deadline_u256,
+            Some(&state_override),
+        )
+        .await
+        {
+            return Err(TradingError::Honeypot(
+                "the simulated Sell reverted, the token could be a honeypot",
+            )
+            .into());
+        }
+
+        let round_trip_amount_out_u256 = QuantTradeHelper::max_simulated_amount_out(
+            ctx,
+            q,
+            &sell_token_in_ctx,
+            &sell_path,
+            amount_in_u256,
+            tx_deadline_u256,
+            DefaultValues::HONEYPOT_CHECK_SEARCH_STEPS,
+            Some(&state_override),
+        )
+        .await;
+
+        let round_trip_loss_perc = divide_into_f256(
+            &amount_in_u256.saturating_sub(round_trip_amount_out_u256),
+            &amount_in_u256,
+        )
+        .mul(ToF256Units::to_f256(&100_u8));
+
+        log::info!(
+            "[honeypot_check] a round trip of {} {} returns about {} {}, a loss of {:.2}%",
+            units_to_human_display(amount_in_u256, token_in_ctx.token_in_decimals),
+            token_in_ctx.token_in_symbol,
+            units_to_human_display(round_trip_amount_out_u256, token_in_ctx.token_in_decimals),
+            token_in_ctx.token_in_symbol,
+            round_trip_loss_perc
+        );
+
+        if round_trip_loss_perc > ToF256Units::to_f256(&max_loss_perc) {
+            log::error!(
+                "[honeypot_check] the round trip loss ({:.2}%) is above {}%",
+                round_trip_loss_perc,
+                max_loss_perc
+            );
+
+            return Err(TradingError::Honeypot(
+                "the simulated round trip loss is above 'honeypot_max_loss_perc', the token could be a honeypot",
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    // the pairs a swap along [path] goes through, with the tokens of every pair sorted like the factory does
+    fn pairs(path: &[Address]) -> Vec<(Address, Address)> {
+        path.windows(2)
+            .map(|d| {
+                if d[0] < d[1] {
+                    (d[0], d[1])
+                } else {
+                    (d[1], d[0])
+                }
+            })
+            .collect()
+    }
+
+    fn has_repeated_pair(path: &[Address]) -> bool {
+        let pairs = pairs(path);
+
+        pairs
+            .iter()
+            .enumerate()
+            .any(|(i, d)| pairs[i + 1..].contains(d))
+    }
+
+    #[test]
+    fn a_round_trip_in_a_single_swap_goes_through_the_same_pair_twice() {
+        let wbnb = Address::from_low_u64_be(1);
+        let token = Address::from_low_u64_be(2);
+
+        // the second hop has no input since the pair already counted the tokens of the first hop in its reserves
+        assert!(has_repeated_pair(&[wbnb, token, wbnb]));
+    }
+
+    #[test]
+    fn the_sell_is_simulated_back_through_the_pairs_of_the_buy_in_its_own_swap() {
+        let busd = Address::from_low_u64_be(1);
+        let wbnb = Address::from_low_u64_be(2);
+        let token = Address::from_low_u64_be(3);
+        let buy_path = vec![busd, wbnb, token];
+
+        let sell_path = HoneypotHelper::sell_path(&buy_path);
+
+        assert_eq!(sell_path, vec![token, wbnb, busd]);
+        assert!(!has_repeated_pair(&buy_path));
+        assert!(!has_repeated_pair(&sell_path));
+
+        let mut sell_pairs = pairs(&sell_path);
+        sell_pairs.reverse();
+        assert_eq!(sell_pairs, pairs(&buy_path));
+    }
+
+    #[test]
+    fn balance_slot_of_a_mapping_at_the_first_slot() {
+        // keccak256(abi.encode(address(0), uint256(0)))
+        assert_eq!(
+            HoneypotHelper::balance_slot(Address::zero(), 0),
+            H256::from_str("ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn allowance_slot_is_the_spender_slot_in_the_mapping_of_the_owner() {
+        let owner = Address::from_low_u64_be(1);
+        let spender = Address::from_low_u64_be(2);
+
+        assert_eq!(
+            HoneypotHelper::allowance_slot(owner, spender, 1),
+            HoneypotHelper::mapping_slot(
+                H256::from(spender),
+                HoneypotHelper::balance_slot(owner, 1)
+            )
+        );
+        assert_ne!(
+            HoneypotHelper::allowance_slot(owner, spender, 1),
+            HoneypotHelper::allowance_slot(spender, owner, 1)
+        );
+    }
+
+    #[test]
+    fn state_override_is_serialized_as_the_state_diff_of_the_token() {
+        let token = Address::from_low_u64_be(1);
+        let slot = H256::from_low_u64_be(2);
+
+        let state_override = HoneypotHelper::state_override(token, slot, U256::from(3));
+
+        assert_eq!(
+            serde_json::to_value(&state_override).unwrap(),
+            serde_json::json!({
+                "0x0000000000000000000000000000000000000001": {
+                    "stateDiff": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000002":
+                            "0x0000000000000000000000000000000000000000000000000000000000000003"
+                    }
+                }
+            })
+        );
+    }
+}
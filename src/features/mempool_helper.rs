@@ -0,0 +1,165 @@
+use crate::common::helpers::urls::get_tx_hash_url;
+use crate::common::models::trade_scheme::TradeSchemeVariant;
+use crate::controllers::quant::quant::{Quant, TradeContext};
+use async_std::stream::StreamExt;
+use ethers::abi::Token;
+use ethers::prelude::Middleware;
+use ethers::types::{Transaction, TxHash, U256};
+
+pub struct MempoolHelper;
+
+/// a pending liquidity add of the token pair found in the mempool
+pub struct PendingLiquidityR {
+    pub tx_hash: TxHash,
+    pub gas_price_gwei: U256,
+    pub amount_of_token_in_u256: U256,
+    pub amount_of_token_out_u256: U256,
+}
+
+impl MempoolHelper {
+    // wait for a pending 'addLiquidity' or 'addLiquidityETH' transaction of the token pair to be sent to the router
+    // returns [None] if the pending transactions subscription stream ends before one is found
+    pub async fn wait_for_pending_liquidity<T>(
+        ctx: &TradeContext<T>,
+        q: &Quant,
+    ) -> anyhow::Result<Option<PendingLiquidityR>>
+    where
+        T: TradeSchemeVariant,
+    {
+        log::debug!("subscribing to the pending transactions...");
+
+        let mut pending_tx_stream = q.provider.ws.subscribe_pending_txs().await?;
+
+        log::info!("waiting for the liquidity to be added in the mempool...");
+
+        while let Some(tx_hash) = pending_tx_stream.next().await {
+            let tx = match q.provider.ws.get_transaction(tx_hash).await {
+                Ok(Some(d)) => d,
+                // the transaction was either dropped or already mined
+                Ok(None) => continue,
+                Err(e) => {
+                    log::debug!("unable to fetch the pending tx ({:?}): {:?}", tx_hash, e);
+
+                    continue;
+                }
+            };
+
+            if let Some(p) = Self::decode_pending_liquidity(ctx, q, &tx) {
+                log::info!(
+                    "detected a pending liquidity add in the mempool, tx hash ({:?}) {}",
+                    p.tx_hash,
+                    get_tx_hash_url(p.tx_hash, q.variables.network_name.clone())
+                );
+
+                return Ok(Some(p));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // decode the router input of the transaction
+    // returns [None] if the transaction doesn't add liquidity to the token pair
+    fn decode_pending_liquidity<T>(
+        ctx: &TradeContext<T>,
+        q: &Quant,
+        tx: &Transaction,
+    ) -> Option<PendingLiquidityR>
+    where
+        T: TradeSchemeVariant,
+    {
+        if tx.to != Some(q.variables.router_in_h160) {
+            return None;
+        }
+
+        let input = tx.input.as_ref();
+
+        if input.len() < 4 {
+            return None;
+        }
+
+        let (selector, data) = input.split_at(4);
+
+        let router_abi = ctx.contracts.router.abi();
+        let add_liquidity_fn = router_abi.function("addLiquidity").ok()?;
+        let add_liquidity_eth_fn = router_abi.function("addLiquidityETH").ok()?;
+
+        let (amount_of_token_in_u256, amount_of_token_out_u256) =
+            if selector == add_liquidity_fn.short_signature() {
+                let tokens = add_liquidity_fn.decode_input(data).ok()?;
+
+                Self::add_liquidity_amounts(ctx, &tokens)?
+            } else if selector == add_liquidity_eth_fn.short_signature() {
+                let tokens = add_liquidity_eth_fn.decode_input(data).ok()?;
+
+                Self::add_liquidity_eth_amounts(ctx, q, &tokens, tx.value)?
+            } else {
+                return None;
+            };
+
+        if amount_of_token_in_u256.is_zero() || amount_of_token_out_u256.is_zero() {
+            return None;
+        }
+
+        log::debug!("pending liquidity tx: {:?}", tx);
+
+        let p = PendingLiquidityR {
+            tx_hash: tx.hash,
+            gas_price_gwei: tx.gas_price,
+            amount_of_token_in_u256,
+            amount_of_token_out_u256,
+        };
+
+        Some(p)
+    }
+
+    // amounts of token in and token out of an 'addLiquidity' call
+    fn add_liquidity_amounts<T>(ctx: &TradeContext<T>, tokens: &[Token]) -> Option<(U256, U256)>
+    where
+        T: TradeSchemeVariant,
+    {
+        let token_a_h160 = Self::token_at(tokens, 0)?.into_address()?;
+        let token_b_h160 = Self::token_at(tokens, 1)?.into_address()?;
+        let amount_a_u256 = Self::token_at(tokens, 2)?.into_uint()?;
+        let amount_b_u256 = Self::token_at(tokens, 3)?.into_uint()?;
+
+        if token_a_h160 == ctx.feed.token_in_h160 && token_b_h160 == ctx.feed.token_out_h160 {
+            return Some((amount_a_u256, amount_b_u256));
+        }
+
+        if token_a_h160 == ctx.feed.token_out_h160 && token_b_h160 == ctx.feed.token_in_h160 {
+            return Some((amount_b_u256, amount_a_u256));
+        }
+
+        None
+    }
+
+    // amounts of token in and token out of an 'addLiquidityETH' call
+    // the native token side of the liquidity is paid as the value of the transaction
+    fn add_liquidity_eth_amounts<T>(
+        ctx: &TradeContext<T>,
+        q: &Quant,
+        tokens: &[Token],
+        tx_value: U256,
+    ) -> Option<(U256, U256)>
+    where
+        T: TradeSchemeVariant,
+    {
+        if ctx.feed.token_in_h160 != q.variables.native_token_h160 {
+            return None;
+        }
+
+        let token_h160 = Self::token_at(tokens, 0)?.into_address()?;
+        let amount_token_u256 = Self::token_at(tokens, 1)?.into_uint()?;
+
+        if token_h160 != ctx.feed.token_out_h160 {
+            return None;
+        }
+
+        Some((tx_value, amount_token_u256))
+    }
+
+    fn token_at(tokens: &[Token], index: usize) -> Option<Token> {
+        tokens.get(index).cloned()
+    }
+}
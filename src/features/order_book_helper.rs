@@ -1,6 +1,15 @@
 use crate::common::helpers::date::get_elapsed_time_ms;
-use crate::features::trade_helper::{AmountInCtx, TokenInCTx};
+use crate::common::models::trade_scheme::{
+    PercentageTradeActuatorForSell, StaticTradeActuatorForSell, TradeActuatorForBuy,
+    TradeActuatorForSell, TrailingStopLossTradeActuatorForSell,
+};
+use crate::common::utils::ether::ether_to_human_display;
+use crate::common::utils::f256::{divide_into_f256, percentage_change_f256, ToF256Units};
+use crate::features::fill_helper::FillR;
+use crate::features::trade_helper::{AmountInCtx, TokenInCTx, TokenOutCTx};
 use ethers::core::types::TransactionReceipt;
+use qd::Quad;
+use std::ops::{Mul, Sub};
 use std::time::Instant;
 
 pub struct OrderBookHelper;
@@ -9,6 +18,18 @@ pub struct OrderBookR {
     pub transaction_receipt: Option<TransactionReceipt>,
     pub token_in_ctx: TokenInCTx,
     pub amount_in_ctx: AmountInCtx,
+    pub token_out_ctx: TokenOutCTx,
+
+    /// the actual fill of the swap, it is [None] in a dry run
+    pub fill: Option<FillR>,
+}
+
+/// the effective entry of a successful Buy trade
+/// it is carried into the Sell function of a BuySell trade
+pub struct BuyEntryR {
+    pub amount_of_token_in: Quad,
+    pub amount_of_token_out_received: Quad,
+    pub entry_price_human_readable_format: Quad,
 }
 
 impl OrderBookHelper {
@@ -22,4 +43,366 @@ impl OrderBookHelper {
 
         time_elapsed_since_token_pairing_ms as i64
     }
+
+    // check whether the live price of [token_out] meets the static trade actuator of the Buy function
+    // [price] is the amount of token_in required to buy a single token_out
+    pub fn is_buy_trade_actuated(
+        trade_actuator: &Option<TradeActuatorForBuy>,
+        price: &Quad,
+    ) -> bool {
+        let s = match trade_actuator {
+            None => return true,
+            Some(TradeActuatorForBuy::Static(d)) => d,
+        };
+
+        if let Some(hold_trade_above_price_u256) = s.hold_trade_above_price_u256 {
+            let hold_trade_above_price = ether_to_human_display(hold_trade_above_price_u256);
+
+            if *price > hold_trade_above_price {
+                log::info!(
+                    "[hold_trade_above_price] the current price ({:.14}) is above {}, holding the Buy trade...",
+                    price,
+                    hold_trade_above_price
+                );
+
+                return false;
+            }
+        }
+
+        if let Some(trade_at_price_u256) = s.trade_at_price_u256 {
+            let trade_at_price = ether_to_human_display(trade_at_price_u256);
+
+            if *price > trade_at_price {
+                log::info!(
+                    "[trade_at_price] the current price ({:.14}) hasn't fallen to {} yet, holding the Buy trade...",
+                    price,
+                    trade_at_price
+                );
+
+                return false;
+            }
+
+            log::info!(
+                "[trade_at_price] the current price ({:.14}) has hit {}",
+                price,
+                trade_at_price
+            );
+        }
+
+        true
+    }
+
+    // check whether an exact output Buy can be carried out within the [amount_of_token_in]
+    pub fn is_buy_exact_output_affordable(
+        token_in_ctx: &TokenInCTx,
+        token_out_ctx: &TokenOutCTx,
+    ) -> bool {
+        let e = match &token_out_ctx.exact_output {
+            None => return true,
+            Some(d) => d,
+        };
+
+        if e.amount_in_u256 > token_in_ctx.amount_of_token_in_u256 {
+            log::info!(
+                "[amount_of_token_out] the estimated amount of {} required ({}) is above {}, holding the Buy trade...",
+                token_in_ctx.token_in_symbol,
+                e.amount_in_in_human_readable_format,
+                token_in_ctx.amount_of_token_in
+            );
+
+            return false;
+        }
+
+        true
+    }
+
+    // check whether the live price of [token_in] meets the trade actuator of the Sell function
+    // [price] is the amount of token_out received for a single token_in
+    // [buy_entry] is required by the percentage trade actuator to compute the profit
+    // [peak_price] keeps the highest price seen by the trailing stop-loss trade actuator across the calls
+    pub fn is_sell_trade_actuated(
+        trade_actuator: &Option<TradeActuatorForSell>,
+        price: &Quad,
+        buy_entry: Option<&BuyEntryR>,
+        peak_price: &mut Option<Quad>,
+    ) -> bool {
+        match trade_actuator {
+            None => true,
+            Some(TradeActuatorForSell::Static(s)) => Self::is_sell_static_trade_actuated(s, price),
+            Some(TradeActuatorForSell::Percentage(p)) => {
+                Self::is_sell_percentage_trade_actuated(p, price, buy_entry)
+            }
+            Some(TradeActuatorForSell::TrailingStopLoss(t)) => {
+                Self::is_sell_trailing_stop_loss_trade_actuated(t, price, buy_entry, peak_price)
+            }
+        }
+    }
+
+    fn is_sell_static_trade_actuated(s: &StaticTradeActuatorForSell, price: &Quad) -> bool {
+        if let Some(hold_trade_below_price_u256) = s.hold_trade_below_price_u256 {
+            let hold_trade_below_price = ether_to_human_display(hold_trade_below_price_u256);
+
+            if *price < hold_trade_below_price {
+                log::info!(
+                    "[hold_trade_below_price] the current price ({:.14}) is below {}, holding the Sell trade...",
+                    price,
+                    hold_trade_below_price
+                );
+
+                return false;
+            }
+        }
+
+        if let Some(trade_at_price_u256) = s.trade_at_price_u256 {
+            let trade_at_price = ether_to_human_display(trade_at_price_u256);
+
+            if *price < trade_at_price {
+                log::info!(
+                    "[trade_at_price] the current price ({:.14}) hasn't risen to {} yet, holding the Sell trade...",
+                    price,
+                    trade_at_price
+                );
+
+                return false;
+            }
+
+            log::info!(
+                "[trade_at_price] the current price ({:.14}) has hit {}",
+                price,
+                trade_at_price
+            );
+        }
+
+        true
+    }
+
+    fn is_sell_percentage_trade_actuated(
+        p: &PercentageTradeActuatorForSell,
+        price: &Quad,
+        buy_entry: Option<&BuyEntryR>,
+    ) -> bool {
+        let entry_price = match buy_entry {
+            None => {
+                log::warn!("the Buy entry price is unavailable, skipping the profit percentage trade actuator...");
+
+                return true;
+            }
+            Some(d) => d.entry_price_human_readable_format,
+        };
+
+        let profit_perc = percentage_change_f256(&entry_price, price);
+
+        log::debug!(
+            "the current price ({:.14}) w.r.t the Buy entry price ({:.14}) is a profit of {:.4}%",
+            price,
+            entry_price,
+            profit_perc
+        );
+
+        if let Some(hold_trade_below_profit_perc) = p.hold_trade_below_profit_perc {
+            if profit_perc < ToF256Units::to_f256(&hold_trade_below_profit_perc) {
+                log::info!(
+                    "[hold_trade_below_profit_perc] the current profit ({:.4}%) is below {}%, holding the Sell trade...",
+                    profit_perc,
+                    hold_trade_below_profit_perc
+                );
+
+                return false;
+            }
+        }
+
+        if let Some(trade_at_profit_perc) = p.trade_at_profit_perc {
+            if profit_perc < ToF256Units::to_f256(&trade_at_profit_perc) {
+                log::info!(
+                    "[trade_at_profit_perc] the current profit ({:.4}%) hasn't risen to {}% yet, holding the Sell trade...",
+                    profit_perc,
+                    trade_at_profit_perc
+                );
+
+                return false;
+            }
+
+            log::info!(
+                "[trade_at_profit_perc] the current profit ({:.4}%) has hit {}%",
+                profit_perc,
+                trade_at_profit_perc
+            );
+        }
+
+        true
+    }
+
+    // the peak starts at the Buy entry price if available, otherwise at the first price seen
+    fn is_sell_trailing_stop_loss_trade_actuated(
+        t: &TrailingStopLossTradeActuatorForSell,
+        price: &Quad,
+        buy_entry: Option<&BuyEntryR>,
+        peak_price: &mut Option<Quad>,
+    ) -> bool {
+        if let Some(stop_loss_floor_price_u256) = t.stop_loss_floor_price_u256 {
+            let stop_loss_floor_price = ether_to_human_display(stop_loss_floor_price_u256);
+
+            if *price <= stop_loss_floor_price {
+                log::info!(
+                    "[stop_loss_floor_price] the current price ({:.14}) has fallen to {}",
+                    price,
+                    stop_loss_floor_price
+                );
+
+                return true;
+            }
+        }
+
+        let mut peak = match (*peak_price, buy_entry) {
+            (Some(p), _) => p,
+            (None, Some(d)) => d.entry_price_human_readable_format,
+            (None, None) => *price,
+        };
+
+        if *price > peak {
+            peak = *price;
+        }
+
+        *peak_price = Some(peak);
+
+        let drawdown_perc =
+            divide_into_f256(&peak.sub(*price), &peak).mul(ToF256Units::to_f256(&100_u8));
+
+        log::debug!(
+            "the current price ({:.14}) w.r.t the peak price ({:.14}) is a drawdown of {:.4}%",
+            price,
+            peak,
+            drawdown_perc
+        );
+
+        if drawdown_perc < ToF256Units::to_f256(&t.trailing_stop_loss_perc) {
+            log::info!(
+                "[trailing_stop_loss_perc] the current drawdown ({:.4}%) from the peak price ({:.14}) hasn't reached {}% yet, holding the Sell trade...",
+                drawdown_perc,
+                peak,
+                t.trailing_stop_loss_perc
+            );
+
+            return false;
+        }
+
+        log::info!(
+            "[trailing_stop_loss_perc] the current drawdown ({:.4}%) from the peak price ({:.14}) has hit {}%",
+            drawdown_perc,
+            peak,
+            t.trailing_stop_loss_perc
+        );
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::utils::ether::decimals_to_ethers;
+    use crate::common::utils::f256::to_f256;
+
+    fn trailing_stop_loss(
+        trailing_stop_loss_perc: u8,
+        stop_loss_floor_price: Option<&str>,
+    ) -> TrailingStopLossTradeActuatorForSell {
+        TrailingStopLossTradeActuatorForSell {
+            trailing_stop_loss_perc,
+            stop_loss_floor_price_u256: stop_loss_floor_price
+                .map(|d| decimals_to_ethers(&d.to_string()).unwrap()),
+        }
+    }
+
+    fn buy_entry(entry_price: &str) -> BuyEntryR {
+        BuyEntryR {
+            amount_of_token_in: to_f256("1"),
+            amount_of_token_out_received: to_f256("1"),
+            entry_price_human_readable_format: to_f256(entry_price),
+        }
+    }
+
+    #[test]
+    fn trailing_stop_loss_tracks_the_peak_from_the_buy_entry() {
+        let t = trailing_stop_loss(10, None);
+        let entry = buy_entry("1.0");
+        let mut peak_price: Option<Quad> = None;
+
+        // a drawdown of 5% from the entry price
+        assert!(!OrderBookHelper::is_sell_trailing_stop_loss_trade_actuated(
+            &t,
+            &to_f256("0.95"),
+            Some(&entry),
+            &mut peak_price
+        ));
+        assert_eq!(peak_price, Some(to_f256("1.0")));
+
+        // the peak moves up with the price
+        assert!(!OrderBookHelper::is_sell_trailing_stop_loss_trade_actuated(
+            &t,
+            &to_f256("1.2"),
+            Some(&entry),
+            &mut peak_price
+        ));
+        assert_eq!(peak_price, Some(to_f256("1.2")));
+
+        // a drawdown of 8.33% from the peak
+        assert!(!OrderBookHelper::is_sell_trailing_stop_loss_trade_actuated(
+            &t,
+            &to_f256("1.1"),
+            Some(&entry),
+            &mut peak_price
+        ));
+        assert_eq!(peak_price, Some(to_f256("1.2")));
+
+        // a drawdown of 12.5% from the peak, while still above the entry price
+        assert!(OrderBookHelper::is_sell_trailing_stop_loss_trade_actuated(
+            &t,
+            &to_f256("1.05"),
+            Some(&entry),
+            &mut peak_price
+        ));
+    }
+
+    #[test]
+    fn trailing_stop_loss_peak_starts_at_the_first_price_without_a_buy_entry() {
+        let t = trailing_stop_loss(10, None);
+        let mut peak_price: Option<Quad> = None;
+
+        assert!(!OrderBookHelper::is_sell_trailing_stop_loss_trade_actuated(
+            &t,
+            &to_f256("2.0"),
+            None,
+            &mut peak_price
+        ));
+        assert_eq!(peak_price, Some(to_f256("2.0")));
+
+        assert!(OrderBookHelper::is_sell_trailing_stop_loss_trade_actuated(
+            &t,
+            &to_f256("1.7"),
+            None,
+            &mut peak_price
+        ));
+    }
+
+    #[test]
+    fn trailing_stop_loss_is_actuated_at_the_floor_price() {
+        let t = trailing_stop_loss(50, Some("0.8"));
+        let mut peak_price: Option<Quad> = None;
+
+        assert!(!OrderBookHelper::is_sell_trailing_stop_loss_trade_actuated(
+            &t,
+            &to_f256("0.81"),
+            None,
+            &mut peak_price
+        ));
+
+        // the drawdown from the peak is far below [trailing_stop_loss_perc]
+        assert!(OrderBookHelper::is_sell_trailing_stop_loss_trade_actuated(
+            &t,
+            &to_f256("0.8"),
+            None,
+            &mut peak_price
+        ));
+    }
 }
@@ -0,0 +1,135 @@
+use crate::common::errors::TradingError;
+use crate::common::models::trade_scheme::TradeSchemeVariant;
+use crate::common::utils::f256::{divide_into_f256, ToF256Units};
+use crate::controllers::contracts::QuantContracts;
+use crate::controllers::quant::quant::{Quant, TradeContext};
+use crate::features::trade_helper::{QuantTradeHelper, TokenInCTx, TokenOutCTx};
+use ethers::abi::Address;
+use ethers::types::U256;
+use qd::Quad;
+use std::ops::{Add, Div, Mul, Sub};
+
+pub struct PriceImpactHelper;
+
+impl PriceImpactHelper {
+    // the share of the value lost to the price impact of a single hop w.r.t the mid price of the pair
+    // the swap fee is excluded
+    pub fn price_impact_from_reserves(amount_in_u256: U256, reserve_in_u256: U256) -> Quad {
+        if amount_in_u256.is_zero() {
+            return ToF256Units::to_f256(&0_u8);
+        }
+
+        divide_into_f256(&amount_in_u256, &reserve_in_u256.add(amount_in_u256))
+            .mul(ToF256Units::to_f256(&100_u8))
+    }
+
+    // fetch the reserves of the pair as ([reserve_in], [reserve_out]) w.r.t the swap direction
+    async fn reserves<T>(
+        ctx: &TradeContext<T>,
+        q: &Quant,
+        token_in_h160: Address,
+        token_out_h160: Address,
+    ) -> anyhow::Result<(U256, U256)>
+    where
+        T: TradeSchemeVariant,
+    {
+        let pair_address =
+            match QuantTradeHelper::get_pair_address(ctx, token_in_h160, token_out_h160).await {
+                None => {
+                    return Err(
+                        TradingError::PriceImpact("a token pair of the path was not found").into(),
+                    )
+                }
+                Some(d) => d,
+            };
+
+        let pair = QuantContracts::pair_contract(&q.middleware.client, pair_address);
+        let (reserve0, reserve1, _) = pair.get_reserves().call().await?;
+        let token0_h160 = pair.token_0().call().await?;
+
+        let (reserve_in_u256, reserve_out_u256) = if token0_h160 == token_in_h160 {
+            (U256::from(reserve0), U256::from(reserve1))
+        } else {
+            (U256::from(reserve1), U256::from(reserve0))
+        };
+
+        log::debug!(
+            "reserves of the pair ({:?}): reserve in: {}, reserve out: {}",
+            pair_address,
+            reserve_in_u256,
+            reserve_out_u256
+        );
+
+        Ok((reserve_in_u256, reserve_out_u256))
+    }
+
+    // compute the price impact of swapping [amount_in_u256] along [path] from the reserves of every pair
+    // the price impacts of the hops compound along the path
+    pub async fn price_impact<T>(
+        ctx: &TradeContext<T>,
+        q: &Quant,
+        path: &[Address],
+        amount_in_u256: U256,
+    ) -> anyhow::Result<Quad>
+    where
+        T: TradeSchemeVariant,
+    {
+        let one = ToF256Units::to_f256(&1_u8);
+        let hundred = ToF256Units::to_f256(&100_u8);
+
+        let mut hop_amount_in_u256 = amount_in_u256;
+        let mut value_retained = one;
+
+        for pair in path.windows(2) {
+            let (reserve_in_u256, reserve_out_u256) =
+                Self::reserves(ctx, q, pair[0], pair[1]).await?;
+
+            let hop_price_impact =
+                Self::price_impact_from_reserves(hop_amount_in_u256, reserve_in_u256);
+            value_retained = value_retained.mul(one.sub(hop_price_impact.div(hundred)));
+
+            hop_amount_in_u256 = QuantTradeHelper::amount_out_from_reserves(
+                hop_amount_in_u256,
+                reserve_in_u256,
+                reserve_out_u256,
+            );
+        }
+
+        Ok(one.sub(value_retained).mul(hundred))
+    }
+
+    // check whether the price impact of the swap is within [max_price_impact_perc]
+    pub async fn is_price_impact_acceptable<T>(
+        ctx: &TradeContext<T>,
+        q: &Quant,
+        token_in_ctx: &TokenInCTx,
+        token_out_ctx: &TokenOutCTx,
+        max_price_impact_perc: u8,
+    ) -> anyhow::Result<bool>
+    where
+        T: TradeSchemeVariant,
+    {
+        // an exact output swap spends the estimated amount of token in
+        let amount_in_u256 = match &token_out_ctx.exact_output {
+            None => token_in_ctx.amount_of_token_in_u256,
+            Some(e) => e.amount_in_u256,
+        };
+
+        let price_impact_perc =
+            Self::price_impact(ctx, q, &token_out_ctx.path, amount_in_u256).await?;
+
+        log::info!("Price Impact: {:.4}%", price_impact_perc);
+
+        if price_impact_perc > ToF256Units::to_f256(&max_price_impact_perc) {
+            log::info!(
+                "[max_price_impact_perc] the price impact of the swap ({:.4}%) is above {}%",
+                price_impact_perc,
+                max_price_impact_perc
+            );
+
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+}
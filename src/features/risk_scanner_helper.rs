@@ -0,0 +1,244 @@
+use crate::common::errors::TradingError;
+use crate::controllers::contracts::{Erc20Contract, QuantContracts};
+use crate::controllers::quant::quant::Quant;
+use ethers::abi::Address;
+use ethers::core::utils::id;
+use ethers::prelude::Middleware;
+use std::str::FromStr;
+
+pub struct RiskScannerHelper;
+
+/// a group of risky admin functions and the risk score added when any of them is found
+struct RiskyFunctions {
+    name: &'static str,
+    signatures: &'static [&'static str],
+    risk_score: u8,
+}
+
+impl RiskScannerHelper {
+    // the 'PUSH4' opcode which pushes a function selector in the dispatcher of a contract
+    const PUSH4_OPCODE: u8 = 0x63;
+
+    // the dead address is used to renounce the ownership as well
+    const DEAD_ADDRESS: &'static str = "0x000000000000000000000000000000000000dEaD";
+
+    // the risk score added when the ownership of the token isn't renounced
+    const OWNERSHIP_RISK_SCORE: u8 = 10;
+
+    // the risk scores of all the groups and [OWNERSHIP_RISK_SCORE] add up to 100
+    fn risky_functions() -> Vec<RiskyFunctions> {
+        vec![
+            RiskyFunctions {
+                name: "mint",
+                signatures: &["mint(address,uint256)", "mint(uint256)"],
+                risk_score: 25,
+            },
+            RiskyFunctions {
+                name: "blacklist",
+                signatures: &[
+                    "blacklist(address)",
+                    "blacklistAddress(address,bool)",
+                    "addToBlacklist(address)",
+                    "setBlacklist(address,bool)",
+                    "addBots(address[])",
+                ],
+                risk_score: 25,
+            },
+            RiskyFunctions {
+                name: "setFee/setTax",
+                signatures: &[
+                    "setFee(uint256)",
+                    "setTax(uint256)",
+                    "setTaxFee(uint256)",
+                    "setTaxFeePercent(uint256)",
+                    "setLiquidityFeePercent(uint256)",
+                    "setBuyFee(uint256)",
+                    "setSellFee(uint256)",
+                ],
+                risk_score: 15,
+            },
+            RiskyFunctions {
+                name: "pause",
+                signatures: &["pause()", "setTradingEnabled(bool)", "setSwapEnabled(bool)"],
+                risk_score: 15,
+            },
+            RiskyFunctions {
+                name: "setMaxTx",
+                signatures: &[
+                    "setMaxTx(uint256)",
+                    "setMaxTxAmount(uint256)",
+                    "setMaxTxPercent(uint256)",
+                ],
+                risk_score: 10,
+            },
+        ]
+    }
+
+    // whether the dispatcher of the contract pushes the selector of the function
+    fn has_function(bytecode: &[u8], signature: &str) -> bool {
+        let selector = id(signature);
+
+        bytecode
+            .windows(5)
+            .any(|w| w[0] == Self::PUSH4_OPCODE && w[1..] == selector)
+    }
+
+    // the risk score of the ownership of the token
+    // tokens without the Ownable 'owner' function don't add to the risk score
+    async fn ownership_risk_score(q: &Quant, token_h160: Address) -> anyhow::Result<u8> {
+        let ownable = QuantContracts::ownable_contract(&q.middleware.client, token_h160);
+
+        let owner_h160 = match ownable.owner().call().await {
+            Ok(d) => d,
+            Err(e) => {
+                log::debug!(
+                    "[risk_scan] unable to fetch the owner of the token: {:?}",
+                    e
+                );
+                log::info!("[risk_scan] the token doesn't implement Ownable");
+
+                return Ok(0);
+            }
+        };
+
+        if owner_h160.is_zero() || owner_h160 == Address::from_str(Self::DEAD_ADDRESS)? {
+            log::info!("[risk_scan] the ownership of the token is renounced");
+
+            return Ok(0);
+        }
+
+        log::warn!(
+            "[risk_scan] the ownership of the token is NOT renounced, owner: {:?}",
+            owner_h160
+        );
+
+        Ok(Self::OWNERSHIP_RISK_SCORE)
+    }
+
+    // scan the bytecode of the token for the selectors of the risky admin functions
+    // returns the risk score of the token between 0 and 100
+    pub async fn scan(
+        q: &Quant,
+        token_erc20: &Erc20Contract,
+        token_symbol: &str,
+    ) -> anyhow::Result<u8> {
+        log::info!("[risk_scan] scanning the contract of {}...", token_symbol);
+
+        let token_h160 = token_erc20.address();
+        let bytecode = q.middleware.client.get_code(token_h160, None).await?;
+
+        if bytecode.as_ref().is_empty() {
+            return Err(
+                TradingError::RiskScan("no contract was found at the token address").into(),
+            );
+        }
+
+        let mut risk_score = 0_u8;
+
+        for f in Self::risky_functions() {
+            let found_signatures: Vec<&str> = f
+                .signatures
+                .iter()
+                .filter(|s| Self::has_function(bytecode.as_ref(), s))
+                .copied()
+                .collect();
+
+            if found_signatures.is_empty() {
+                continue;
+            }
+
+            log::warn!(
+                "[risk_scan] found the '{}' functions: {}",
+                f.name,
+                found_signatures.join(", ")
+            );
+
+            risk_score += f.risk_score;
+        }
+
+        risk_score += Self::ownership_risk_score(q, token_h160).await?;
+
+        log::info!(
+            "[risk_scan] risk score of {}: {}/100",
+            token_symbol,
+            risk_score
+        );
+
+        Ok(risk_score)
+    }
+
+    // refuse the token if its risk score is above [max_risk_score]
+    pub async fn check(
+        q: &Quant,
+        token_erc20: &Erc20Contract,
+        token_symbol: &str,
+        max_risk_score: Option<u8>,
+    ) -> anyhow::Result<()> {
+        let risk_score = Self::scan(q, token_erc20, token_symbol).await?;
+
+        if let Some(m) = max_risk_score {
+            if risk_score > m {
+                log::error!(
+                    "[risk_scan] the risk score of {} ({}) is above {}",
+                    token_symbol,
+                    risk_score,
+                    m
+                );
+
+                return Err(TradingError::RiskScan(
+                    "the risk score of the token is above 'max_risk_score'",
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSFER_SIGNATURE: &str = "transfer(address,uint256)";
+
+    // a dispatcher entry: PUSH4 <selector> DUP2 EQ
+    fn dispatcher_bytecode(selector: [u8; 4]) -> Vec<u8> {
+        let mut bytecode = vec![0x60, 0x80, 0x60, 0x40, 0x52, 0x80];
+        bytecode.push(RiskScannerHelper::PUSH4_OPCODE);
+        bytecode.extend_from_slice(&selector);
+        bytecode.extend_from_slice(&[0x81, 0x14]);
+
+        bytecode
+    }
+
+    #[test]
+    fn has_function_finds_the_pushed_selector() {
+        let bytecode = dispatcher_bytecode(id(TRANSFER_SIGNATURE));
+
+        assert!(RiskScannerHelper::has_function(
+            &bytecode,
+            TRANSFER_SIGNATURE
+        ));
+        assert!(!RiskScannerHelper::has_function(
+            &bytecode,
+            "mint(address,uint256)"
+        ));
+    }
+
+    #[test]
+    fn has_function_ignores_the_selector_bytes_not_pushed_by_push4() {
+        let mut bytecode = dispatcher_bytecode(id(TRANSFER_SIGNATURE));
+        bytecode[6] = 0x00;
+
+        assert!(!RiskScannerHelper::has_function(
+            &bytecode,
+            TRANSFER_SIGNATURE
+        ));
+    }
+
+    #[test]
+    fn has_function_of_an_empty_bytecode() {
+        assert!(!RiskScannerHelper::has_function(&[], TRANSFER_SIGNATURE));
+    }
+}
@@ -0,0 +1,374 @@
+use crate::common::helpers::urls::get_tx_hash_url;
+use crate::common::models::trade_scheme::SellScheme;
+use crate::common::utils::f256::{divide_into_f256, ToF256Units};
+use crate::controllers::contracts::{PairContract, QuantContracts};
+use crate::controllers::quant::quant::{Quant, TradeContext};
+use async_std::stream::StreamExt;
+use ethers::abi::{Address, Token};
+use ethers::prelude::Middleware;
+use ethers::providers::{SubscriptionStream, Ws};
+use ethers::types::{Block, Transaction, TxHash, U256};
+use qd::Quad;
+use std::future::Future;
+use std::ops::Mul;
+
+/// a liquidity removal of the pair detected by the rug pull watcher
+#[derive(Debug, Clone)]
+pub struct RugPullR {
+    pub liquidity_drop_perc: Quad,
+
+    /// the gas price of the pending liquidity removal.
+    /// it is [None] if the drop was found in the reserves of the pair
+    pub gas_price_gwei: Option<U256>,
+}
+
+/// watches the liquidity of the pair of [token_in] while the Sell function waits.
+/// the reserves of the pair are checked in every new block and
+/// the pending liquidity removals of the pair are looked up in the mempool
+pub struct RugPullWatcher<'a> {
+    ctx: &'a TradeContext<SellScheme>,
+    q: &'a Quant,
+    pair: PairContract,
+    pair_h160: Address,
+    paired_token_h160: Address,
+    is_paired_token_token0: bool,
+    initial_paired_reserve_u256: U256,
+    liquidity_drop_perc: u8,
+    block_stream: SubscriptionStream<'a, Ws, Block<TxHash>>,
+    pending_tx_stream: SubscriptionStream<'a, Ws, TxHash>,
+    rug_pull: Option<RugPullR>,
+}
+
+impl<'a> RugPullWatcher<'a> {
+    pub async fn new(
+        ctx: &'a TradeContext<SellScheme>,
+        q: &'a Quant,
+        pair_h160: Address,
+        paired_token_h160: Address,
+        liquidity_drop_perc: u8,
+    ) -> anyhow::Result<RugPullWatcher<'a>> {
+        log::debug!("initializing the rug pull watcher...");
+
+        let pair = QuantContracts::pair_contract(&q.middleware.client, pair_h160);
+        let is_paired_token_token0 = pair.token_0().call().await? == paired_token_h160;
+
+        let block_stream = q.provider.ws.subscribe_blocks().await?;
+        let pending_tx_stream = q.provider.ws.subscribe_pending_txs().await?;
+
+        let mut w = RugPullWatcher {
+            ctx,
+            q,
+            pair,
+            pair_h160,
+            paired_token_h160,
+            is_paired_token_token0,
+            initial_paired_reserve_u256: U256::zero(),
+            liquidity_drop_perc,
+            block_stream,
+            pending_tx_stream,
+            rug_pull: None,
+        };
+
+        // the liquidity drop is measured against the reserve of the paired token at the start of the watch
+        w.initial_paired_reserve_u256 = w.paired_reserve().await?;
+
+        log::info!(
+            "[rug_pull_watcher] watching the liquidity of the pair ({:?}), initial reserve of the paired token: {}",
+            pair_h160,
+            w.initial_paired_reserve_u256
+        );
+
+        Ok(w)
+    }
+
+    // the detected rug pull, if any
+    // once detected, the watcher stops waiting for anything else
+    pub fn rug_pull(&self) -> Option<&RugPullR> {
+        self.rug_pull.as_ref()
+    }
+
+    // wait for [f] while watching the liquidity of the pair
+    // [f] is cancelled as soon as the liquidity drops by [liquidity_drop_perc]
+    pub async fn watch_until<F>(&mut self, f: F) -> anyhow::Result<()>
+    where
+        F: Future<Output = anyhow::Result<()>>,
+    {
+        if self.rug_pull.is_some() {
+            return Ok(());
+        }
+
+        tokio::pin!(f);
+
+        loop {
+            tokio::select! {
+                r = &mut f => {
+                    return r;
+                }
+                Some(block) = self.block_stream.next() => {
+                    log::debug!("[rug_pull_watcher] received a new block: {:?}", block.number);
+
+                    if let Some(r) = self.check_reserves().await {
+                        self.detected(r);
+
+                        return Ok(());
+                    }
+                }
+                Some(tx_hash) = self.pending_tx_stream.next() => {
+                    if let Some(r) = self.check_pending_tx(tx_hash).await {
+                        self.detected(r);
+
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    fn detected(&mut self, r: RugPullR) {
+        log::warn!(
+            "[rug_pull_watcher] the liquidity of the pair is dropping by {:.2}%, triggering an emergency Sell...",
+            r.liquidity_drop_perc
+        );
+
+        self.rug_pull = Some(r);
+    }
+
+    async fn paired_reserve(&self) -> anyhow::Result<U256> {
+        let (reserve0, reserve1, _) = self.pair.get_reserves().call().await?;
+
+        if self.is_paired_token_token0 {
+            Ok(U256::from(reserve0))
+        } else {
+            Ok(U256::from(reserve1))
+        }
+    }
+
+    fn drop_perc(part_u256: U256, total_u256: U256) -> Quad {
+        if total_u256.is_zero() {
+            return ToF256Units::to_f256(&100_u8);
+        }
+
+        divide_into_f256(&part_u256, &total_u256).mul(ToF256Units::to_f256(&100_u8))
+    }
+
+    fn is_drop_above_limit(&self, liquidity_drop_perc: &Quad) -> bool {
+        *liquidity_drop_perc >= ToF256Units::to_f256(&self.liquidity_drop_perc)
+    }
+
+    // a failure to fetch the reserves doesn't stop the watch, the next block is checked again
+    async fn check_reserves(&self) -> Option<RugPullR> {
+        let paired_reserve_u256 = match self.paired_reserve().await {
+            Ok(d) => d,
+            Err(e) => {
+                log::debug!("[rug_pull_watcher] unable to fetch the reserves: {:?}", e);
+
+                return None;
+            }
+        };
+
+        let liquidity_drop_perc = Self::drop_perc(
+            self.initial_paired_reserve_u256
+                .saturating_sub(paired_reserve_u256),
+            self.initial_paired_reserve_u256,
+        );
+
+        log::debug!(
+            "[rug_pull_watcher] reserve of the paired token: {}, drop: {:.2}%",
+            paired_reserve_u256,
+            liquidity_drop_perc
+        );
+
+        if !self.is_drop_above_limit(&liquidity_drop_perc) {
+            return None;
+        }
+
+        Some(RugPullR {
+            liquidity_drop_perc,
+            gas_price_gwei: None,
+        })
+    }
+
+    // the liquidity drop of a pending removal is the share of the LP tokens it burns
+    async fn check_pending_tx(&self, tx_hash: TxHash) -> Option<RugPullR> {
+        let tx = match self.q.provider.ws.get_transaction(tx_hash).await {
+            Ok(Some(d)) => d,
+            // the transaction was either dropped or already mined
+            _ => return None,
+        };
+
+        let liquidity_u256 = self.decode_pending_liquidity_removal(&tx)?;
+
+        let total_supply_u256 = match self.pair.total_supply().call().await {
+            Ok(d) => d,
+            Err(e) => {
+                log::debug!(
+                    "[rug_pull_watcher] unable to fetch the total supply of the pair: {:?}",
+                    e
+                );
+
+                return None;
+            }
+        };
+
+        let liquidity_drop_perc = Self::drop_perc(liquidity_u256, total_supply_u256);
+
+        log::info!(
+            "[rug_pull_watcher] detected a pending liquidity removal of {:.2}% in the mempool, tx hash ({:?}) {}",
+            liquidity_drop_perc,
+            tx.hash,
+            get_tx_hash_url(tx.hash, self.q.variables.network_name.clone())
+        );
+
+        if !self.is_drop_above_limit(&liquidity_drop_perc) {
+            return None;
+        }
+
+        Some(RugPullR {
+            liquidity_drop_perc,
+            gas_price_gwei: Some(tx.gas_price),
+        })
+    }
+
+    // decode the amount of LP tokens removed by the transaction
+    // a removal is either a 'removeLiquidity*' call to the router or a transfer of the LP tokens to the pair,
+    // which are burnt by the pair right after
+    // returns [None] if the transaction doesn't remove the liquidity of the pair
+    fn decode_pending_liquidity_removal(&self, tx: &Transaction) -> Option<U256> {
+        let input = tx.input.as_ref();
+
+        if input.len() < 4 {
+            return None;
+        }
+
+        let (selector, data) = input.split_at(4);
+
+        if tx.to == Some(self.q.variables.router_in_h160) {
+            let router_abi = self.ctx.contracts.router.abi();
+
+            for name in &["removeLiquidity", "removeLiquidityWithPermit"] {
+                let f = router_abi.function(name).ok()?;
+
+                if selector == f.short_signature() {
+                    let tokens = f.decode_input(data).ok()?;
+
+                    return self.remove_liquidity_amount(&tokens);
+                }
+            }
+
+            for name in &[
+                "removeLiquidityETH",
+                "removeLiquidityETHWithPermit",
+                "removeLiquidityETHSupportingFeeOnTransferTokens",
+                "removeLiquidityETHWithPermitSupportingFeeOnTransferTokens",
+            ] {
+                let f = router_abi.function(name).ok()?;
+
+                if selector == f.short_signature() {
+                    let tokens = f.decode_input(data).ok()?;
+
+                    return self.remove_liquidity_eth_amount(&tokens);
+                }
+            }
+
+            return None;
+        }
+
+        if tx.to == Some(self.pair_h160) {
+            let pair_abi = self.pair.abi();
+            let transfer_fn = pair_abi.function("transfer").ok()?;
+            let transfer_from_fn = pair_abi.function("transferFrom").ok()?;
+
+            let (to_h160, value_u256) = if selector == transfer_fn.short_signature() {
+                let tokens = transfer_fn.decode_input(data).ok()?;
+
+                (
+                    Self::token_at(&tokens, 0)?.into_address()?,
+                    Self::token_at(&tokens, 1)?.into_uint()?,
+                )
+            } else if selector == transfer_from_fn.short_signature() {
+                let tokens = transfer_from_fn.decode_input(data).ok()?;
+
+                (
+                    Self::token_at(&tokens, 1)?.into_address()?,
+                    Self::token_at(&tokens, 2)?.into_uint()?,
+                )
+            } else {
+                return None;
+            };
+
+            if to_h160 != self.pair_h160 {
+                return None;
+            }
+
+            return Some(value_u256);
+        }
+
+        None
+    }
+
+    // the liquidity of a 'removeLiquidity' call
+    fn remove_liquidity_amount(&self, tokens: &[Token]) -> Option<U256> {
+        let token_in_h160 = self.ctx.feed.token_in_h160;
+
+        let token_a_h160 = Self::token_at(tokens, 0)?.into_address()?;
+        let token_b_h160 = Self::token_at(tokens, 1)?.into_address()?;
+
+        let is_pair = (token_a_h160 == token_in_h160 && token_b_h160 == self.paired_token_h160)
+            || (token_a_h160 == self.paired_token_h160 && token_b_h160 == token_in_h160);
+
+        if !is_pair {
+            return None;
+        }
+
+        Self::token_at(tokens, 2)?.into_uint()
+    }
+
+    // the liquidity of a 'removeLiquidityETH' call
+    // the other side of the pair is always the native token
+    fn remove_liquidity_eth_amount(&self, tokens: &[Token]) -> Option<U256> {
+        if self.paired_token_h160 != self.q.variables.native_token_h160 {
+            return None;
+        }
+
+        let token_h160 = Self::token_at(tokens, 0)?.into_address()?;
+
+        if token_h160 != self.ctx.feed.token_in_h160 {
+            return None;
+        }
+
+        Self::token_at(tokens, 1)?.into_uint()
+    }
+
+    fn token_at(tokens: &[Token], index: usize) -> Option<Token> {
+        tokens.get(index).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drop_perc_of_the_reserve() {
+        assert_eq!(
+            RugPullWatcher::drop_perc(U256::from(25), U256::from(100)),
+            ToF256Units::to_f256(&25_u8)
+        );
+        assert_eq!(
+            RugPullWatcher::drop_perc(U256::from(100), U256::from(100)),
+            ToF256Units::to_f256(&100_u8)
+        );
+        assert_eq!(
+            RugPullWatcher::drop_perc(U256::zero(), U256::from(100)),
+            ToF256Units::to_f256(&0_u8)
+        );
+    }
+
+    #[test]
+    fn drop_perc_of_an_empty_reserve_is_a_full_drop() {
+        assert_eq!(
+            RugPullWatcher::drop_perc(U256::zero(), U256::zero()),
+            ToF256Units::to_f256(&100_u8)
+        );
+    }
+}
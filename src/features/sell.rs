@@ -1,27 +1,37 @@
 use crate::common::constants::default_values::DefaultValues;
 use crate::common::errors::{OrderBookError, TradingError};
 use crate::common::helpers::date::get_elapsed_time_ms;
-use crate::common::helpers::timer::{interruptable_sleep, tokio_sleep};
+use crate::common::helpers::gas::increase_gas_price_by;
+use crate::common::helpers::timer::tokio_sleep;
 use crate::common::helpers::urls::get_tx_hash_url;
-use crate::common::models::trade_scheme::{SellScheme, TradeSchemeVariant};
-use crate::common::utils::ether::decimals_to_ethers;
+use crate::common::models::trade_scheme::{
+    SellScheme, TakeProfitStep, TradeActuatorForSell, TradeSchemeVariant,
+};
+use crate::common::utils::ether::{
+    decimals_to_units, gwei_to_human_display, units_to_human_display,
+};
 use crate::common::utils::f256::percentage_of_f256;
+use crate::common::utils::u256::percentage_of_u256;
 use crate::controllers::cli::entry_points::EntryPoints;
 use crate::controllers::quant::quant::{Quant, TradeContext};
-use crate::features::order_book_helper::{OrderBookHelper, OrderBookR};
+use crate::features::fill_helper::FillHelper;
+use crate::features::order_book_helper::{BuyEntryR, OrderBookHelper, OrderBookR};
+use crate::features::price_impact_helper::PriceImpactHelper;
+use crate::features::rug_pull_helper::{RugPullR, RugPullWatcher};
+use crate::features::take_profit_ladder_helper::{
+    TakeProfitLadderHelper, TakeProfitLadderState, TakeProfitStepCtx,
+};
+use crate::features::tax_helper::{TaxHelper, TaxR};
 use crate::features::trade_helper::{
-    AmountInCtx, AmountOutCtx, GasCtx, QuantTradeHelper, TokenPairR,
+    AmountInCtx, AmountOutCtx, GasCtx, GasTxCtx, PendingSwapCtx, QuantTradeHelper, TokenPairR,
 };
-use ethers::middleware::SignerMiddleware;
-use ethers::prelude::builders::ContractCall;
-use ethers::providers::Provider;
-use ethers::signers::LocalWallet;
 use ethers::types::{TransactionReceipt, U256};
-use min_max::max;
+use min_max::{max, min};
+use qd::Quad;
 use std::time::Instant;
 
 pub struct QuantSell {
-    transaction_receipt: Option<TransactionReceipt>,
+    pub transaction_receipt: Option<TransactionReceipt>,
 }
 
 impl QuantSell {
@@ -30,7 +40,9 @@ impl QuantSell {
         q: &Quant,
     ) -> anyhow::Result<AmountInCtx> {
         let amount_of_token_in = &ctx.scheme.amount_of_token_in.to_owned();
-        let amount_of_token_in_u256 = ctx.scheme.amount_of_token_in_u256()?;
+        let amount_of_token_in_u256 = ctx
+            .scheme
+            .amount_of_token_in_u256(ctx.contracts.token_in_decimals)?;
         let perc_of_token_in = &ctx.scheme.perc_of_token_in.to_owned();
 
         let mut amount_of_token_in_computed: String = Default::default();
@@ -72,8 +84,10 @@ impl QuantSell {
 
                     let amount_of_token_after_perc_correction =
                         percentage_of_f256(&token_balance_in_account_quad, perc);
-                    let amount_of_token_after_perc_human_readable_format =
-                        decimals_to_ethers(&amount_of_token_after_perc_correction)?;
+                    let amount_of_token_after_perc_human_readable_format = decimals_to_units(
+                        &amount_of_token_after_perc_correction,
+                        ctx.contracts.token_in_decimals,
+                    )?;
 
                     amount_of_token_in_u256_computed =
                         amount_of_token_after_perc_human_readable_format;
@@ -89,33 +103,52 @@ impl QuantSell {
             amount_of_token_in: amount_of_token_in_computed,
             amount_of_token_in_u256: amount_of_token_in_u256_computed,
             slippage: ctx.scheme.slippage as u64,
+            perc_increase_slippage: ctx.scheme.perc_increase_slippage,
+            max_slippage: ctx.scheme.max_slippage,
             token_in_contract: ctx.feed.token_in_contract.to_owned(),
             token_in_h160: ctx.feed.token_in_h160,
             token_in_symbol: ctx.scheme.token_in_symbol.to_owned(),
+            token_in_decimals: ctx.contracts.token_in_decimals,
         };
 
         Ok(a)
     }
 
-    async fn amount_token_out(
+    // the amount of token in sold by a single step of the take-profit ladder
+    fn amount_token_in_for_take_profit_step(
         ctx: &TradeContext<SellScheme>,
         amount_of_token_in_u256: U256,
-    ) -> anyhow::Result<AmountOutCtx> {
-        let amount_out_list_contract_call: ContractCall<
-            SignerMiddleware<Provider<_>, LocalWallet>,
-            Vec<U256>,
-        > = ctx.contracts.router.get_amounts_out(
+    ) -> AmountInCtx {
+        let amount_of_token_in =
+            units_to_human_display(amount_of_token_in_u256, ctx.contracts.token_in_decimals);
+
+        AmountInCtx {
+            amount_of_token_in: amount_of_token_in.to_string(),
             amount_of_token_in_u256,
-            vec![ctx.feed.token_in_h160, ctx.feed.token_out_h160],
-        );
+            slippage: ctx.scheme.slippage as u64,
+            perc_increase_slippage: ctx.scheme.perc_increase_slippage,
+            max_slippage: ctx.scheme.max_slippage,
+            token_in_contract: ctx.feed.token_in_contract.to_owned(),
+            token_in_h160: ctx.feed.token_in_h160,
+            token_in_symbol: ctx.scheme.token_in_symbol.to_owned(),
+            token_in_decimals: ctx.contracts.token_in_decimals,
+        }
+    }
 
-        let amount_out_list = amount_out_list_contract_call.call().await?;
+    async fn amount_token_out(
+        ctx: &TradeContext<SellScheme>,
+        amount_of_token_in_u256: U256,
+    ) -> anyhow::Result<AmountOutCtx> {
+        let (path, amount_out_list) =
+            QuantTradeHelper::amount_out_list(ctx, amount_of_token_in_u256).await?;
 
         let a = AmountOutCtx {
+            path,
             amount_out_list,
             token_out_contract: ctx.feed.token_out_contract.to_owned(),
             token_out_symbol: ctx.scheme.token_out_symbol.to_owned(),
             token_out_h160: ctx.feed.token_out_h160,
+            token_out_decimals: ctx.contracts.token_out_decimals,
         };
 
         Ok(a)
@@ -125,8 +158,9 @@ impl QuantSell {
         let g = GasCtx {
             gas_price: ctx.scheme.gas_price.to_owned(),
             gas_price_gwei: ctx.scheme.gas_price_gwei()?,
-            gas_limit: ctx.scheme.gas_limit,
-            gas_limit_u256: ctx.scheme.gas_limit_u256()?,
+            gas_price_multiplier_perc: ctx.scheme.gas_price_multiplier_perc,
+            min_gas_price_gwei: ctx.scheme.min_gas_price_gwei()?,
+            gas_limit_ctx: QuantTradeHelper::gas_limit_ctx(&ctx.scheme)?,
             tx_timeout_in_ms: ctx.scheme.tx_timeout_in_ms,
             max_gas_price: ctx.scheme.max_gas_price.to_owned(),
             max_gas_price_gwei: ctx.scheme.max_gas_price_gwei()?,
@@ -136,11 +170,55 @@ impl QuantSell {
         Ok(g)
     }
 
+    // the emergency Sell of a rug pull has to be mined before the liquidity removal
+    // the gas price is increased over both the current gas price and the gas price of the pending removal
+    fn bump_gas_price_for_rug_pull(
+        ctx: &TradeContext<SellScheme>,
+        gas_ctx: &GasCtx,
+        gas_tx_ctx: &mut GasTxCtx,
+        rug_pull: &RugPullR,
+    ) -> anyhow::Result<()> {
+        let base_gas_price_gwei = match rug_pull.gas_price_gwei {
+            None => gas_tx_ctx.gas_price_gwei,
+            Some(d) => max!(d, gas_tx_ctx.gas_price_gwei),
+        };
+
+        let (_, mut gas_price_gwei) = increase_gas_price_by(
+            gwei_to_human_display(base_gas_price_gwei),
+            ctx.scheme.rug_pull_perc_increase_gas_price() as u64,
+            None,
+        )?;
+
+        if let Some(max_gas_price_gwei) = gas_ctx.max_gas_price_gwei {
+            if gas_price_gwei > max_gas_price_gwei {
+                log::warn!(
+                    "the gas price of the emergency Sell ({} gwei) has crossed the max allowed gas price, will continue with the max allowed gas price...",
+                    gwei_to_human_display(gas_price_gwei)
+                );
+
+                gas_price_gwei = max_gas_price_gwei;
+            }
+        }
+
+        gas_tx_ctx.gas_price_gwei = gas_price_gwei;
+        gas_tx_ctx.gas_price = gwei_to_human_display(gas_price_gwei).to_string();
+
+        log::info!(
+            "[rug_pull_watcher] increasing the gas price of the emergency Sell to: {} gwei",
+            gas_tx_ctx.gas_price
+        );
+
+        Ok(())
+    }
+
     async fn order_book(
         ctx: &TradeContext<SellScheme>,
         q: &Quant,
         entry_points: &EntryPoints,
         token_pair: &TokenPairR,
+        buy_entry: Option<&BuyEntryR>,
+        take_profit_step: Option<TakeProfitStepCtx>,
+        mut rug_pull_watcher: Option<&mut RugPullWatcher<'_>>,
     ) -> anyhow::Result<OrderBookR> {
         log::debug!("initializing the order book...");
 
@@ -169,20 +247,35 @@ impl QuantSell {
                     sleep_for_ms
                 );
 
-                //////////////////////////////
-                //////////////////////////////
-                //////////////////////////////
-                // todo: this is a temporary hack to interrupt the the 'First Sell Attempt'
-                //  remove this while converting this whole thing into a micro service
-                interruptable_sleep(
-                    DefaultValues::SELL_INTERRUPTER_KEYWORD.parse()?,
-                    ctx.scheme.name.clone(),
-                    sleep_for_ms,
-                )
-                .await?;
-                //////////////////////////////
-                //////////////////////////////
-                //////////////////////////////
+                match rug_pull_watcher.as_deref_mut() {
+                    // the wait is cancelled as soon as a rug pull is detected
+                    // the 'First Sell Attempt' cannot be interrupted from the stdin while the rug pull watcher is active
+                    Some(w) => {
+                        w.watch_until(async {
+                            tokio_sleep(sleep_for_ms).await;
+
+                            Ok(())
+                        })
+                        .await?;
+                    }
+                    None => {
+                        //////////////////////////////
+                        //////////////////////////////
+                        //////////////////////////////
+                        // todo: this is a temporary hack to interrupt the the 'First Sell Attempt'
+                        //  remove this while converting this whole thing into a micro service
+                        q.interrupter
+                            .interruptable_sleep(
+                                DefaultValues::SELL_INTERRUPTER_KEYWORD,
+                                ctx.scheme.name.clone(),
+                                sleep_for_ms,
+                            )
+                            .await;
+                        //////////////////////////////
+                        //////////////////////////////
+                        //////////////////////////////
+                    }
+                }
 
                 log::debug!("[wait_time_before_first_tx_attempt_in_ms] waking up from the 'First Sell Attempt' sleep");
             } else {
@@ -191,27 +284,183 @@ impl QuantSell {
         }
 
         // [amount_in_ctx] and [token_in_ctx] are fetched after the [wait_time_before_first_tx_attempt_in_ms] sleep in the 'Sell' function because we could manually sell off some tokens in between the BuySell function
-        // create token in amount context
-        let amount_in_ctx = Self::amount_token_in(ctx, q).await?;
+        // a step of the take-profit ladder sells its own share of the position on its own trigger
+        let (amount_in_ctx, trade_actuator) = match take_profit_step {
+            None => (
+                Self::amount_token_in(ctx, q).await?,
+                ctx.scheme.trade_actuator()?,
+            ),
+            Some(s) => {
+                log::info!(
+                    "[take_profit_ladder] waiting for the trigger of the step #{}...",
+                    s.step_number
+                );
+
+                (
+                    Self::amount_token_in_for_take_profit_step(ctx, s.amount_of_token_in_u256),
+                    s.trade_actuator,
+                )
+            }
+        };
 
         // create token in context
         let token_in_ctx = QuantTradeHelper::token_in(&amount_in_ctx)?;
 
+        // the profit percentage trade actuator requires the entry price of the Buy trade
+        if let Some(TradeActuatorForSell::Percentage(_)) = trade_actuator {
+            match buy_entry {
+                None => {
+                    if !entry_points.dry_run {
+                        return Err(OrderBookError::Sell(
+                            "the Buy entry price is required by the profit percentage trade actuator",
+                        )
+                        .into());
+                    }
+                }
+                Some(d) => {
+                    log::info!(
+                        "Buy entry price: {:.14} {} per {}",
+                        d.entry_price_human_readable_format,
+                        ctx.scheme.token_out_symbol,
+                        ctx.scheme.token_in_symbol
+                    );
+                }
+            }
+        }
+
+        let mut tax_r: Option<TaxR> = None;
+
+        // the highest price seen by the trailing stop-loss trade actuator
+        let mut peak_price: Option<Quad> = None;
+
         let mut trade_attempt_count = 1_u64;
+        let mut pending_swap: Option<PendingSwapCtx> = None;
         let first_trade_attempt_instant = Instant::now();
 
         loop {
             log::debug!("attempting to sell #{}...", trade_attempt_count);
             let current_trade_attempt_instant = Instant::now();
 
+            // an emergency Sell of a rug pull is neither held nor aborted
+            let rug_pull = rug_pull_watcher
+                .as_deref()
+                .and_then(|w| w.rug_pull())
+                .cloned();
+
             // token amount out handler
             let amount_out_ctx =
                 Self::amount_token_out(ctx, token_in_ctx.amount_of_token_in_u256).await?;
-            let token_out_ctx = QuantTradeHelper::token_out(&amount_in_ctx, &amount_out_ctx)?;
+            let mut token_out_ctx =
+                QuantTradeHelper::token_out(&amount_in_ctx, &amount_out_ctx, trade_attempt_count)?;
+
+            // the price impact of the swap is computed from the reserves of the pairs along the path
+            let is_price_impact_acceptable = match ctx.scheme.max_price_impact_perc {
+                None => true,
+                Some(m) => {
+                    PriceImpactHelper::is_price_impact_acceptable(
+                        ctx,
+                        q,
+                        &token_in_ctx,
+                        &token_out_ctx,
+                        m,
+                    )
+                    .await?
+                }
+            };
+
+            if !entry_points.dry_run
+                && rug_pull.is_none()
+                && !is_price_impact_acceptable
+                && ctx.scheme.is_abort_on_max_price_impact_active()
+            {
+                log::debug!("terminating the Sell trade...");
+
+                return Err(OrderBookError::Sell(
+                    "the price impact of the swap is above 'max_price_impact_perc'",
+                )
+                .into());
+            }
+
+            // hold the trade until the live price meets the trade actuator
+            // the trade is also held while the price impact is above [max_price_impact_perc]
+            if !entry_points.dry_run
+                && rug_pull.is_none()
+                && (!OrderBookHelper::is_sell_trade_actuated(
+                    &trade_actuator,
+                    &token_out_ctx.price_of_token_in_per_token_out_human_readable_format,
+                    buy_entry,
+                    &mut peak_price,
+                ) || !is_price_impact_acceptable)
+            {
+                // if the [max_allowed_time_for_trading_in_ms] is available then check whether the max allowed trading time has reached
+                if let Some(max_allowed_time_for_trading_in_ms_ok) =
+                    max_allowed_time_for_trading_in_ms
+                {
+                    let time_elapsed_since_token_pairing_ms =
+                        OrderBookHelper::time_since_token_paired(&token_paired_time);
+
+                    if time_elapsed_since_token_pairing_ms >= max_allowed_time_for_trading_in_ms_ok
+                    {
+                        log::debug!("terminating the Sell trade...");
+
+                        return Err(OrderBookError::Sell(
+                            "exhausted the max allowed time while holding the Sell trade for the trade actuator",
+                        )
+                        .into());
+                    }
+                }
+
+                // the trailing stop-loss trade actuator follows the price block by block
+                let wait_for_next_poll = async {
+                    if let Some(TradeActuatorForSell::TrailingStopLoss(_)) = trade_actuator {
+                        return QuantTradeHelper::wait_for_next_block(q).await;
+                    }
+
+                    tokio_sleep(DefaultValues::TRADE_ACTUATOR_POLLING_INTERVAL_IN_MS).await;
+
+                    Ok(())
+                };
+
+                // the hold is cancelled as soon as a rug pull is detected
+                match rug_pull_watcher.as_deref_mut() {
+                    None => wait_for_next_poll.await?,
+                    Some(w) => w.watch_until(wait_for_next_poll).await?,
+                }
+
+                continue;
+            }
 
             // gas price handler
             let gas_ctx = Self::gas(ctx)?;
-            let gas_tx_ctx = QuantTradeHelper::gas(&gas_ctx, trade_attempt_count)?;
+            let mut gas_tx_ctx = QuantTradeHelper::gas(q, &gas_ctx, trade_attempt_count).await?;
+
+            if let Some(r) = &rug_pull {
+                Self::bump_gas_price_for_rug_pull(ctx, &gas_ctx, &mut gas_tx_ctx, r)?;
+            }
+
+            // the transfer tax is detected once, before the first swap is sent
+            if tax_r.is_none() && ctx.scheme.is_auto_tax_detection_active() {
+                tax_r = Some(
+                    TaxHelper::tax(
+                        ctx,
+                        q,
+                        &token_in_ctx,
+                        &token_out_ctx,
+                        &gas_tx_ctx,
+                        ctx.scheme.is_token_out_deflationary,
+                    )
+                    .await,
+                );
+            }
+
+            let is_deflationary_token = match &tax_r {
+                None => ctx.scheme.is_token_out_deflationary,
+                Some(t) => {
+                    TaxHelper::adjust_amount_out_min(&mut token_out_ctx, t);
+
+                    t.is_deflationary_token
+                }
+            };
 
             log::debug!("\n");
             QuantTradeHelper::print_info(&token_in_ctx, &token_out_ctx, &gas_tx_ctx, q);
@@ -225,6 +474,8 @@ impl QuantSell {
                     transaction_receipt: None,
                     token_in_ctx,
                     amount_in_ctx,
+                    token_out_ctx,
+                    fill: None,
                 };
 
                 return Ok(order_r);
@@ -234,9 +485,10 @@ impl QuantSell {
                 ctx,
                 &token_in_ctx,
                 &token_out_ctx,
-                &gas_tx_ctx,
+                &mut gas_tx_ctx,
                 q,
-                ctx.scheme.is_token_out_deflationary,
+                is_deflationary_token,
+                &mut pending_swap,
             )
             .await;
 
@@ -269,10 +521,20 @@ impl QuantSell {
                         elapsed_time_since_first_trade_attempt
                     );
 
+                    let fill = FillHelper::fill(
+                        q,
+                        &token_in_ctx,
+                        &token_out_ctx,
+                        &gas_tx_ctx,
+                        &swap_tx_receipt,
+                    );
+
                     let order_r = OrderBookR {
                         transaction_receipt: Some(swap_tx_receipt),
                         token_in_ctx,
                         amount_in_ctx,
+                        token_out_ctx,
+                        fill,
                     };
 
                     return Ok(order_r);
@@ -320,6 +582,8 @@ impl QuantSell {
                         trade_attempt_count
                     );
 
+                    QuantTradeHelper::warn_pending_swap(&pending_swap);
+
                     return Err(OrderBookError::Sell(
                         "exhausted the retry attempts to carry out the Sell trade",
                     )
@@ -340,6 +604,8 @@ impl QuantSell {
                     OrderBookHelper::time_since_token_paired(&token_paired_time);
 
                 if time_elapsed_since_token_pairing_ms >= max_allowed_time_for_trading_in_ms_ok {
+                    QuantTradeHelper::warn_pending_swap(&pending_swap);
+
                     return Err(OrderBookError::Sell(
                         "exhausted the max allowed time to carry out the Sell trade",
                     )
@@ -359,23 +625,209 @@ impl QuantSell {
         }
     }
 
+    // carry out the steps of the take-profit ladder in the listed order
+    // the progress is saved after every step so that a restart doesn't repeat the completed steps
+    async fn take_profit_ladder(
+        ctx: &TradeContext<SellScheme>,
+        q: &Quant,
+        entry_points: &EntryPoints,
+        token_pair: &TokenPairR,
+        buy_entry: Option<&BuyEntryR>,
+        take_profit_ladder: &[TakeProfitStep],
+        mut rug_pull_watcher: Option<&mut RugPullWatcher<'_>>,
+    ) -> anyhow::Result<Option<TransactionReceipt>> {
+        log::debug!("initializing the take-profit ladder...");
+
+        let ladder_file_path = TakeProfitLadderHelper::file_path(ctx, q, take_profit_ladder)?;
+
+        // the position of a BuySell trade is the one the Buy has just entered,
+        // the progress left over from a previous position is dropped
+        if buy_entry.is_some() {
+            TakeProfitLadderHelper::remove(&ladder_file_path)?;
+        }
+
+        let mut state = match TakeProfitLadderHelper::load(&ladder_file_path)? {
+            Some(d) => {
+                log::info!(
+                    "[take_profit_ladder] resuming the take-profit ladder, completed steps: {:?}",
+                    d.completed_steps
+                );
+
+                d
+            }
+            None => {
+                // the position is the amount of token in at the start of the ladder
+                let amount_in_ctx = Self::amount_token_in(ctx, q).await?;
+
+                TakeProfitLadderState {
+                    position_u256: amount_in_ctx.amount_of_token_in_u256,
+                    remaining_position_u256: amount_in_ctx.amount_of_token_in_u256,
+                    completed_steps: vec![],
+                }
+            }
+        };
+
+        log::info!(
+            "[take_profit_ladder] position: {} {}, remaining: {} {}",
+            units_to_human_display(state.position_u256, ctx.contracts.token_in_decimals),
+            ctx.scheme.token_in_symbol,
+            units_to_human_display(
+                state.remaining_position_u256,
+                ctx.contracts.token_in_decimals
+            ),
+            ctx.scheme.token_in_symbol
+        );
+
+        let mut transaction_receipt = None;
+
+        for (i, step) in take_profit_ladder.iter().enumerate() {
+            let step_number = i + 1;
+
+            if state.completed_steps.contains(&step_number) {
+                log::info!(
+                    "[take_profit_ladder] the step #{} was already completed, skipping...",
+                    step_number
+                );
+
+                continue;
+            }
+
+            let mut amount_of_token_in_u256 =
+                percentage_of_u256(state.position_u256, step.perc_of_position as u64);
+
+            // the account may hold less than the position after the taxes and the rounding of the earlier steps
+            if !entry_points.dry_run {
+                let (_, token_balance_in_account_u256) =
+                    QuantTradeHelper::get_token_balance_in_account(q, &ctx.contracts).await?;
+
+                amount_of_token_in_u256 =
+                    min!(amount_of_token_in_u256, token_balance_in_account_u256);
+
+                if amount_of_token_in_u256.is_zero() {
+                    return Err(OrderBookError::Sell(
+                        "no tokens are left in the account to carry out the take-profit ladder",
+                    )
+                    .into());
+                }
+            }
+
+            let take_profit_step = TakeProfitStepCtx {
+                step_number,
+                amount_of_token_in_u256,
+                trade_actuator: step.trade_actuator()?,
+            };
+
+            let o = Self::order_book(
+                ctx,
+                q,
+                entry_points,
+                token_pair,
+                buy_entry,
+                Some(take_profit_step),
+                rug_pull_watcher.as_deref_mut(),
+            )
+            .await?;
+
+            if entry_points.dry_run {
+                continue;
+            }
+
+            transaction_receipt = o.transaction_receipt;
+
+            state.remaining_position_u256 = state
+                .remaining_position_u256
+                .saturating_sub(amount_of_token_in_u256);
+            state.completed_steps.push(step_number);
+
+            TakeProfitLadderHelper::save(&ladder_file_path, &state)?;
+
+            log::info!(
+                "[take_profit_ladder] the step #{} was completed, remaining position: {} {}",
+                step_number,
+                units_to_human_display(
+                    state.remaining_position_u256,
+                    ctx.contracts.token_in_decimals
+                ),
+                ctx.scheme.token_in_symbol
+            );
+
+            // the rest of the position is sold right away once a rug pull is detected
+            if rug_pull_watcher
+                .as_deref()
+                .and_then(|w| w.rug_pull())
+                .is_some()
+            {
+                log::warn!(
+                    "[take_profit_ladder] selling the rest of the position after the rug pull..."
+                );
+
+                let (_, token_balance_in_account_u256) =
+                    QuantTradeHelper::get_token_balance_in_account(q, &ctx.contracts).await?;
+
+                if !token_balance_in_account_u256.is_zero() {
+                    let take_profit_step = TakeProfitStepCtx {
+                        step_number,
+                        amount_of_token_in_u256: token_balance_in_account_u256,
+                        trade_actuator: None,
+                    };
+
+                    let o = Self::order_book(
+                        ctx,
+                        q,
+                        entry_points,
+                        token_pair,
+                        buy_entry,
+                        Some(take_profit_step),
+                        rug_pull_watcher.as_deref_mut(),
+                    )
+                    .await?;
+
+                    transaction_receipt = o.transaction_receipt;
+                }
+
+                break;
+            }
+        }
+
+        if !entry_points.dry_run {
+            // a finished ladder starts afresh on the next run
+            TakeProfitLadderHelper::remove(&ladder_file_path)?;
+
+            log::info!(
+                "[take_profit_ladder] all the steps of the take-profit ladder were completed"
+            );
+        }
+
+        Ok(transaction_receipt)
+    }
+
     pub async fn new(
         ctx: &TradeContext<SellScheme>,
         q: &Quant,
         entry_points: &EntryPoints,
+        buy_entry: Option<&BuyEntryR>,
     ) -> anyhow::Result<Self> {
         log::debug!("initializing the 'Sell' function...");
 
+        // the liquidity of [token_in] is added against the second token of the route
+        let pair_route = QuantTradeHelper::pair_route(ctx).await?;
+        let paired_token_h160 = pair_route[1];
+        let min_liquidity_required_u256 = ctx
+            .scheme
+            .min_liquidity_required_u256(ctx.contracts.token_in_decimals)?;
+
         // create token pair address
         // the method will wait until the token pair address is created if not found
-        let pair_address = QuantTradeHelper::create_pair(ctx).await?;
+        let pair_address =
+            QuantTradeHelper::create_pair(ctx, ctx.feed.token_in_h160, paired_token_h160).await?;
 
         // create token pair value
         // the method will wait until the minimum required liquidity is found
         let token_pair = QuantTradeHelper::token_pair_value(
-            &ctx.contracts,
+            &ctx.contracts.token_in_erc20,
+            ctx.contracts.token_in_decimals,
             pair_address,
-            &ctx.scheme.min_liquidity_required_u256()?,
+            &min_liquidity_required_u256,
         )
         .await?;
 
@@ -384,16 +836,48 @@ impl QuantSell {
             &ctx.contracts,
             q,
             ctx.scheme.approve_gas_price_gwei()?,
-            ctx.scheme.approve_gas_limit_u256()?,
+            QuantTradeHelper::approve_gas_limit_ctx(&ctx.scheme)?,
             ctx.scheme.token_in_symbol.to_owned(),
         )
         .await?;
 
+        // the rug pull watcher runs alongside the waits of the Sell function
+        let mut rug_pull_watcher = match ctx.scheme.rug_pull_liquidity_drop_perc {
+            None => None,
+            Some(p) => Some(RugPullWatcher::new(ctx, q, pair_address, paired_token_h160, p).await?),
+        };
+
         // create the order sell
-        let o = Self::order_book(ctx, q, entry_points, &token_pair).await?;
+        let transaction_receipt = match &ctx.scheme.take_profit_ladder {
+            None => {
+                Self::order_book(
+                    ctx,
+                    q,
+                    entry_points,
+                    &token_pair,
+                    buy_entry,
+                    None,
+                    rug_pull_watcher.as_mut(),
+                )
+                .await?
+                .transaction_receipt
+            }
+            Some(d) => {
+                Self::take_profit_ladder(
+                    ctx,
+                    q,
+                    entry_points,
+                    &token_pair,
+                    buy_entry,
+                    d,
+                    rug_pull_watcher.as_mut(),
+                )
+                .await?
+            }
+        };
 
         let q_sell = QuantSell {
-            transaction_receipt: o.transaction_receipt,
+            transaction_receipt,
         };
 
         Ok(q_sell)
@@ -0,0 +1,154 @@
+use crate::common::constants::file_paths::FilePaths;
+use crate::common::models::config::NetworkName;
+use crate::common::models::trade_scheme::{SellScheme, TakeProfitStep, TradeActuatorForSell};
+use crate::common::utils::files::file::{create_directory, exists};
+use crate::controllers::quant::quant::{Quant, TradeContext};
+use ethers::abi::ethereum_types::H64;
+use ethers::abi::Address;
+use ethers::core::utils::keccak256;
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+use std::fs::{remove_file, File};
+
+pub struct TakeProfitLadderHelper;
+
+/// a single step of the take-profit ladder handed over to the Sell order book
+pub struct TakeProfitStepCtx {
+    pub step_number: usize,
+    pub amount_of_token_in_u256: U256,
+    pub trade_actuator: Option<TradeActuatorForSell>,
+}
+
+/// the progress of a take-profit ladder
+/// it is saved after every step so that a restart doesn't repeat the completed steps
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TakeProfitLadderState {
+    pub position_u256: U256,
+    pub remaining_position_u256: U256,
+    pub completed_steps: Vec<usize>,
+}
+
+impl TakeProfitLadderHelper {
+    // a ladder is identified by the network, the account, the token pair it sells and its steps
+    // so that an edited ladder doesn't resume from the progress of the previous one
+    pub fn file_path(
+        ctx: &TradeContext<SellScheme>,
+        q: &Quant,
+        take_profit_ladder: &[TakeProfitStep],
+    ) -> anyhow::Result<String> {
+        Self::ladder_file_path(
+            &q.variables.network_name,
+            q.variables.account_address_h160,
+            ctx.feed.token_in_h160,
+            ctx.feed.token_out_h160,
+            take_profit_ladder,
+        )
+    }
+
+    fn ladder_file_path(
+        network_name: &NetworkName,
+        account_h160: Address,
+        token_in_h160: Address,
+        token_out_h160: Address,
+        take_profit_ladder: &[TakeProfitStep],
+    ) -> anyhow::Result<String> {
+        let steps_digest = keccak256(serde_json::to_vec(take_profit_ladder)?);
+
+        Ok(format!(
+            "{}/{}-{:?}-{:?}-{:?}-{:?}.yaml",
+            FilePaths::TAKE_PROFIT_LADDERS,
+            network_name,
+            account_h160,
+            token_in_h160,
+            token_out_h160,
+            H64::from_slice(&steps_digest[..8])
+        ))
+    }
+
+    // returns [None] if the ladder wasn't started yet
+    pub fn load(file_path: &str) -> anyhow::Result<Option<TakeProfitLadderState>> {
+        if !exists(file_path) {
+            return Ok(None);
+        }
+
+        let f = File::open(file_path)?;
+        let state: TakeProfitLadderState = serde_yaml::from_reader(f)?;
+
+        Ok(Some(state))
+    }
+
+    pub fn save(file_path: &str, state: &TakeProfitLadderState) -> anyhow::Result<()> {
+        create_directory(FilePaths::TAKE_PROFIT_LADDERS)?;
+
+        let f = File::create(file_path)?;
+        serde_yaml::to_writer(f, state)?;
+
+        Ok(())
+    }
+
+    pub fn remove(file_path: &str) -> anyhow::Result<()> {
+        if exists(file_path) {
+            remove_file(file_path)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(trade_at_profit_perc: u8, perc_of_position: u8) -> TakeProfitStep {
+        TakeProfitStep {
+            trade_at_price: None,
+            trade_at_profit_perc: Some(trade_at_profit_perc),
+            perc_of_position,
+        }
+    }
+
+    fn file_path(network_name: &NetworkName, take_profit_ladder: &[TakeProfitStep]) -> String {
+        TakeProfitLadderHelper::ladder_file_path(
+            network_name,
+            Address::from_low_u64_be(1),
+            Address::from_low_u64_be(2),
+            Address::from_low_u64_be(3),
+            take_profit_ladder,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn same_ladder_resumes_from_the_same_file() {
+        let ladder = vec![step(50, 50), step(100, 100)];
+
+        assert_eq!(
+            file_path(&NetworkName::Mainnet, &ladder),
+            file_path(&NetworkName::Mainnet, &ladder.clone())
+        );
+    }
+
+    #[test]
+    fn ladder_on_another_network_doesnt_share_the_file() {
+        let ladder = vec![step(50, 50), step(100, 100)];
+
+        assert_ne!(
+            file_path(&NetworkName::Mainnet, &ladder),
+            file_path(&NetworkName::Testnet, &ladder)
+        );
+    }
+
+    #[test]
+    fn edited_ladder_doesnt_share_the_file() {
+        let ladder = vec![step(50, 50), step(100, 100)];
+
+        assert_ne!(
+            file_path(&NetworkName::Mainnet, &ladder),
+            file_path(&NetworkName::Mainnet, &[step(50, 25), step(100, 100)])
+        );
+        assert_ne!(
+            file_path(&NetworkName::Mainnet, &ladder),
+            file_path(&NetworkName::Mainnet, &[step(100, 100), step(50, 50)])
+        );
+    }
+}
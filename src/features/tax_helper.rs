@@ -0,0 +1,236 @@
+use crate::common::constants::default_values::DefaultValues;
+use crate::common::errors::TradingError;
+use crate::common::models::trade_scheme::TradeSchemeVariant;
+use crate::common::utils::ether::units_to_human_display;
+use crate::common::utils::f256::{divide_into_f256, ToF256Units};
+use crate::controllers::quant::quant::{Quant, TradeContext};
+use crate::features::trade_helper::{GasTxCtx, QuantTradeHelper, TokenInCTx, TokenOutCTx};
+use ethers::types::U256;
+use qd::Quad;
+use std::ops::{Div, Mul};
+
+pub struct TaxHelper;
+
+pub struct TaxR {
+    pub is_deflationary_token: bool,
+    pub amount_out_quoted_u256: U256,
+    pub amount_out_received_u256: U256,
+    pub tax_perc: Quad,
+}
+
+impl TaxHelper {
+    // compare the 'getAmountsOut' quote with the amount actually received in a simulated swap
+    // any shortfall is the transfer tax taken by the token contracts along the path
+    async fn detect<T>(
+        ctx: &TradeContext<T>,
+        q: &Quant,
+        token_in_ctx: &TokenInCTx,
+        token_out_ctx: &TokenOutCTx,
+        gas_tx_ctx: &GasTxCtx,
+    ) -> anyhow::Result<TaxR>
+    where
+        T: TradeSchemeVariant,
+    {
+        let amount_out_quoted_u256 = token_out_ctx.amount_out_max_u256;
+        let tx_deadline_u256 = gas_tx_ctx.tx_deadline_u256;
+
+        // the full quote is received when there is no transfer tax
+        if QuantTradeHelper::is_simulated_swap_successful(
+            ctx,
+            q,
+            token_in_ctx,
+            &token_out_ctx.path,
+            amount_out_quoted_u256,
+            tx_deadline_u256,
+            None,
+        )
+        .await
+        {
+            let t = TaxR {
+                is_deflationary_token: false,
+                amount_out_quoted_u256,
+                amount_out_received_u256: amount_out_quoted_u256,
+                tax_perc: ToF256Units::to_f256(&0_u8),
+            };
+
+            return Ok(t);
+        }
+
+        if !QuantTradeHelper::is_simulated_swap_successful(
+            ctx,
+            q,
+            token_in_ctx,
+            &token_out_ctx.path,
+            U256::zero(),
+            tx_deadline_u256,
+            None,
+        )
+        .await
+        {
+            return Err(TradingError::TaxDetection("the simulated swap reverted").into());
+        }
+
+        let amount_out_received_u256 = QuantTradeHelper::max_simulated_amount_out(
+            ctx,
+            q,
+            token_in_ctx,
+            &token_out_ctx.path,
+            amount_out_quoted_u256,
+            tx_deadline_u256,
+            DefaultValues::TAX_DETECTION_SEARCH_STEPS,
+            None,
+        )
+        .await;
+        let tax_perc = divide_into_f256(
+            &amount_out_quoted_u256.saturating_sub(amount_out_received_u256),
+            &amount_out_quoted_u256,
+        )
+        .mul(ToF256Units::to_f256(&100_u8));
+
+        let t = TaxR {
+            is_deflationary_token: true,
+            amount_out_quoted_u256,
+            amount_out_received_u256,
+            tax_perc,
+        };
+
+        Ok(t)
+    }
+
+    // detect the transfer tax of the swap before the first swap is sent
+    // falls back to [is_deflationary_token_default] if the swap could not be simulated
+    pub async fn tax<T>(
+        ctx: &TradeContext<T>,
+        q: &Quant,
+        token_in_ctx: &TokenInCTx,
+        token_out_ctx: &TokenOutCTx,
+        gas_tx_ctx: &GasTxCtx,
+        is_deflationary_token_default: bool,
+    ) -> TaxR
+    where
+        T: TradeSchemeVariant,
+    {
+        log::info!(
+            "[auto_detect_tax] simulating a swap of {} for {}...",
+            token_in_ctx.token_in_symbol,
+            token_out_ctx.token_out_symbol
+        );
+
+        match Self::detect(ctx, q, token_in_ctx, token_out_ctx, gas_tx_ctx).await {
+            Ok(t) => {
+                log::info!(
+                    "[auto_detect_tax] quoted {} {}, received {} {}, a transfer tax of {:.2}%",
+                    units_to_human_display(
+                        t.amount_out_quoted_u256,
+                        token_out_ctx.token_out_decimals
+                    ),
+                    token_out_ctx.token_out_symbol,
+                    units_to_human_display(
+                        t.amount_out_received_u256,
+                        token_out_ctx.token_out_decimals
+                    ),
+                    token_out_ctx.token_out_symbol,
+                    t.tax_perc
+                );
+
+                if t.is_deflationary_token {
+                    log::info!("[auto_detect_tax] found a deflationary token, the fee on transfer swap will be used");
+                }
+
+                t
+            }
+            Err(e) => {
+                log::error!("{:?}", e);
+                log::warn!(
+                    "[auto_detect_tax] falling back to 'is_token_out_deflationary': {}",
+                    is_deflationary_token_default
+                );
+
+                TaxR {
+                    is_deflationary_token: is_deflationary_token_default,
+                    amount_out_quoted_u256: U256::zero(),
+                    amount_out_received_u256: U256::zero(),
+                    tax_perc: ToF256Units::to_f256(&0_u8),
+                }
+            }
+        }
+    }
+
+    // scale [amount_out_min] down by the measured tax so that the slippage tolerance applies to the amount actually received
+    pub fn adjust_amount_out_min(token_out_ctx: &mut TokenOutCTx, tax_r: &TaxR) {
+        if tax_r.amount_out_quoted_u256.is_zero()
+            || tax_r.amount_out_received_u256 >= tax_r.amount_out_quoted_u256
+        {
+            return;
+        }
+
+        let amount_out_min_u256 = token_out_ctx
+            .amount_out_min_u256
+            .mul(tax_r.amount_out_received_u256)
+            .div(tax_r.amount_out_quoted_u256);
+
+        token_out_ctx.amount_out_min_u256 = amount_out_min_u256;
+        token_out_ctx.amount_out_min_in_human_readable_format =
+            units_to_human_display(amount_out_min_u256, token_out_ctx.token_out_decimals);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::Address;
+
+    fn token_out_ctx(amount_out_min_u256: U256) -> TokenOutCTx {
+        TokenOutCTx {
+            path: vec![],
+            amount_out_min_u256,
+            amount_out_max_u256: amount_out_min_u256,
+            token_out_symbol: "TOKEN".to_string(),
+            token_out_contract: "".to_string(),
+            token_out_h160: Address::zero(),
+            token_out_decimals: 18,
+            amount_out_min_in_human_readable_format: Quad::from(0),
+            amount_out_max_in_human_readable_format: Quad::from(0),
+            price_of_token_out_per_token_in_human_readable_format: Quad::from(0),
+            price_of_token_in_per_token_out_human_readable_format: Quad::from(0),
+            slippage: 0,
+            exact_output: None,
+        }
+    }
+
+    fn tax_r(amount_out_quoted: u64, amount_out_received: u64) -> TaxR {
+        TaxR {
+            is_deflationary_token: amount_out_received < amount_out_quoted,
+            amount_out_quoted_u256: U256::from(amount_out_quoted),
+            amount_out_received_u256: U256::from(amount_out_received),
+            tax_perc: Quad::from(0),
+        }
+    }
+
+    #[test]
+    fn adjust_amount_out_min_scales_down_by_the_tax() {
+        let mut ctx = token_out_ctx(U256::from(1000));
+
+        TaxHelper::adjust_amount_out_min(&mut ctx, &tax_r(100, 90));
+
+        assert_eq!(ctx.amount_out_min_u256, U256::from(900));
+    }
+
+    #[test]
+    fn adjust_amount_out_min_without_a_tax() {
+        let mut ctx = token_out_ctx(U256::from(1000));
+
+        TaxHelper::adjust_amount_out_min(&mut ctx, &tax_r(100, 100));
+
+        assert_eq!(ctx.amount_out_min_u256, U256::from(1000));
+    }
+
+    #[test]
+    fn adjust_amount_out_min_without_a_quote() {
+        let mut ctx = token_out_ctx(U256::from(1000));
+
+        TaxHelper::adjust_amount_out_min(&mut ctx, &tax_r(0, 0));
+
+        assert_eq!(ctx.amount_out_min_u256, U256::from(1000));
+    }
+}
@@ -1,16 +1,107 @@
+use crate::common::errors::TradingError;
 use crate::common::helpers::date::get_elapsed_time_ms;
+use crate::common::models::trade_scheme::{BuyScheme, SellScheme};
 use crate::controllers::cli::entry_points::EntryPoints;
-use crate::controllers::quant::quant::{Quant, QuantTrading};
+use crate::controllers::quant::quant::{Quant, QuantTrading, TradeContext};
 use crate::features::buy::QuantBuy;
+use crate::features::order_book_helper::BuyEntryR;
 use crate::features::sell::QuantSell;
+use min_max::max;
+use std::sync::Arc;
 use std::time::Instant;
 
 pub struct QuantTrade;
 
 impl QuantTrade {
+    fn print_dry_run_info(trading_ctx: &QuantTrading) {
+        log::debug!("printing dry run...");
+
+        if let Some(bucket) = &trading_ctx.buy_context {
+            for d in bucket {
+                log::info!(
+                    "'token_in_contract' for Buy function: {:?} ({})",
+                    d.feed.token_in_contract,
+                    d.scheme.token_in_symbol
+                );
+                log::info!(
+                    "'token_out_contract' for Buy function: {:?} ({})",
+                    d.feed.token_out_contract,
+                    d.scheme.token_in_symbol
+                );
+            }
+        }
+
+        if let Some(bucket) = &trading_ctx.sell_context {
+            for d in bucket {
+                log::info!(
+                    "'token_in_contract' for Sell function: {:?} ({})",
+                    d.feed.token_in_contract,
+                    d.scheme.token_in_symbol
+                );
+                log::info!(
+                    "'token_out_contract' for Sell function: {:?} ({})",
+                    d.feed.token_out_contract,
+                    d.scheme.token_in_symbol
+                );
+            }
+        }
+    }
+
+    // carry out a single trade entry
+    // in a BuySell trade the Sell is carried out right after its paired Buy
+    async fn trade(
+        q: Arc<Quant>,
+        entry_points: Arc<EntryPoints>,
+        buy_ctx: Option<Arc<TradeContext<BuyScheme>>>,
+        sell_ctx: Option<Arc<TradeContext<SellScheme>>>,
+        trade_number: usize,
+    ) -> anyhow::Result<()> {
+        let trade_start_time = Instant::now();
+
+        let mut buy_entry: Option<BuyEntryR> = None;
+
+        if let Some(d) = &buy_ctx {
+            log::info!(
+                "[trade #{}] starting the Buy trade of {}...",
+                trade_number,
+                d.scheme.token_out_symbol
+            );
+
+            let buy = QuantBuy::new(d, &q, &entry_points).await?;
+
+            buy_entry = buy.buy_entry;
+
+            let elapsed_trade_time = get_elapsed_time_ms(&trade_start_time);
+            log::debug!(
+                "[trade #{}] elapsed Buy trade time: {:?} milliseconds",
+                trade_number,
+                elapsed_trade_time
+            );
+        }
+
+        if let Some(d) = &sell_ctx {
+            log::info!(
+                "[trade #{}] starting the Sell trade of {}...",
+                trade_number,
+                d.scheme.token_in_symbol
+            );
+
+            let _ = QuantSell::new(d, &q, &entry_points, buy_entry.as_ref()).await?;
+
+            let elapsed_trade_time = get_elapsed_time_ms(&trade_start_time);
+            log::debug!(
+                "[trade #{}] elapsed Sell trade time: {:?} milliseconds",
+                trade_number,
+                elapsed_trade_time
+            );
+        }
+
+        Ok(())
+    }
+
     pub async fn new(
-        q: &Quant,
-        entry_points: &EntryPoints,
+        q: &Arc<Quant>,
+        entry_points: &Arc<EntryPoints>,
         trading_ctx: &QuantTrading,
     ) -> anyhow::Result<Self> {
         let trade_start_time = Instant::now();
@@ -22,69 +113,74 @@ impl QuantTrade {
 
         // <dry run print info>
         if entry_points.dry_run {
-            log::debug!("printing dry run...");
-
-            if let Some(bucket) = &trading_ctx.buy_context {
-                if let Some(d) = bucket.first() {
-                    log::info!(
-                        "'token_in_contract' for Buy function: {:?} ({})",
-                        d.feed.token_in_contract,
-                        d.scheme.token_in_symbol
-                    );
-                    log::info!(
-                        "'token_out_contract' for Buy function: {:?} ({})",
-                        d.feed.token_out_contract,
-                        d.scheme.token_in_symbol
-                    );
-                }
-            }
-
-            if let Some(bucket) = &trading_ctx.sell_context {
-                if let Some(d) = bucket.first() {
-                    log::info!(
-                        "'token_in_contract' for Sell function: {:?} ({})",
-                        d.feed.token_in_contract,
-                        d.scheme.token_in_symbol
-                    );
-                    log::info!(
-                        "'token_out_contract' for Sell function: {:?} ({})",
-                        d.feed.token_out_contract,
-                        d.scheme.token_in_symbol
-                    );
-                }
-            }
+            Self::print_dry_run_info(trading_ctx);
         }
         // <!dry run print info>
 
-        if let Some(bucket) = &trading_ctx.buy_context {
-            if let Some(d) = bucket.first() {
-                let buy = QuantBuy::new(d, q, entry_points).await?;
-            }
+        let buy_bucket = trading_ctx.buy_context.to_owned().unwrap_or_default();
+        let sell_bucket = trading_ctx.sell_context.to_owned().unwrap_or_default();
 
-            let elapsed_trade_time = get_elapsed_time_ms(&trade_start_time);
-            log::debug!(
-                "elapsed Buy trade time: {:?} milliseconds",
-                elapsed_trade_time
-            );
+        // the Buy and the Sell of the same position are paired in a BuySell trade
+        // only one of the buckets is available in the Buy and the Sell trades
+        let trades_count = max!(buy_bucket.len(), sell_bucket.len());
+
+        log::debug!("starting {} trade(s)...", trades_count);
+
+        // every trade entry is spawned as its own task so that multiple tokens are traded at the same time
+        let mut trade_handles = vec![];
+        for i in 0..trades_count {
+            let trade_handle = tokio::spawn(Self::trade(
+                q.clone(),
+                entry_points.clone(),
+                buy_bucket.get(i).cloned(),
+                sell_bucket.get(i).cloned(),
+                i + 1,
+            ));
+
+            trade_handles.push(trade_handle);
         }
 
-        if let Some(bucket) = &trading_ctx.sell_context {
-            if let Some(d) = bucket.first() {
-                let sell = QuantSell::new(d, q, entry_points).await?;
+        let mut failed_trades_count = 0;
+        for (i, trade_handle) in trade_handles.into_iter().enumerate() {
+            let trade_number = i + 1;
+
+            match trade_handle.await {
+                Ok(Ok(_)) => {
+                    log::debug!("[trade #{}] the trade has finished", trade_number);
+                }
+                Ok(Err(e)) => {
+                    failed_trades_count += 1;
+
+                    log::error!("[trade #{}] the trade has failed: {:?}", trade_number, e);
+                }
+                Err(e) => {
+                    failed_trades_count += 1;
+
+                    log::error!(
+                        "[trade #{}] the trade task has failed: {:?}",
+                        trade_number,
+                        e
+                    );
+                }
             }
-            let elapsed_trade_time = get_elapsed_time_ms(&trade_start_time);
-            log::debug!(
-                "elapsed Sell trade time: {:?} milliseconds",
-                elapsed_trade_time
-            );
         }
 
+        log::info!(
+            "{} out of {} trade(s) were successful",
+            trades_count - failed_trades_count,
+            trades_count
+        );
+
         let elapsed_trade_time = get_elapsed_time_ms(&trade_start_time);
         log::debug!(
             "elapsed trading time: {:?} milliseconds",
             elapsed_trade_time
         );
 
+        if failed_trades_count > 0 {
+            return Err(TradingError::Trades("some of the trades were unsuccessful").into());
+        }
+
         Ok(Self)
     }
 }
@@ -1,23 +1,35 @@
 use crate::common::constants::default_values::DefaultValues;
 use crate::common::errors::TradingError;
-use crate::common::helpers::gas::increase_gas_price_by;
+use crate::common::helpers::date::get_elapsed_time_ms;
+use crate::common::helpers::ethers::{get_account_balance, get_network_gas_price};
+use crate::common::helpers::gas::{auto_gas_price, gas_limit_with_margin, increase_gas_price_by};
+use crate::common::helpers::timer::tokio_sleep;
 use crate::common::helpers::urls::get_tx_hash_url;
+use crate::common::models::ledger::{Ledger, LedgerEntry};
 use crate::common::models::trade_scheme::TradeSchemeVariant;
 use crate::common::types::ChronoDuration;
-use crate::common::utils::ether::ether_to_human_display;
+use crate::common::utils::ether::{
+    ether_to_human_display, gwei_to_human_display, units_to_human_display,
+};
 use crate::common::utils::f256::{divide_into_f256, to_f256};
 use crate::common::utils::u256::percentage_of_u256;
-use crate::controllers::contracts::{Erc20Contract, QuantContracts};
+use crate::controllers::contracts::{Erc20Contract, Erc20TransferFilter, QuantContracts};
 use crate::controllers::quant::quant::{Quant, TradeContext};
-use ethers::abi::ethereum_types::U256;
-use ethers::abi::Address;
+use async_std::stream::StreamExt;
+use ethers::abi::ethereum_types::{H256, U256};
+use ethers::abi::{Address, RawLog};
 use ethers::contract::builders::ContractCall;
-use ethers::core::types::TransactionReceipt;
+use ethers::contract::EthEvent;
+use ethers::core::types::{TransactionReceipt, TransactionRequest};
 use ethers::middleware::SignerMiddleware;
 use ethers::prelude::{LocalWallet, Middleware, Provider, Ws};
-use min_max::max;
+use ethers::providers::JsonRpcClient;
+use ethers::types::Bytes;
+use min_max::{max, min};
 use qd::Quad;
-use std::ops::{Add, Sub};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::{Add, Div, Mul, Sub};
 use std::time::Instant;
 
 pub struct QuantTradeHelper;
@@ -27,29 +39,45 @@ pub struct AmountInCtx {
     pub amount_of_token_in: String,
     pub amount_of_token_in_u256: U256,
     pub slippage: u64,
+    pub perc_increase_slippage: Option<u32>,
+    pub max_slippage: Option<u8>,
     pub token_in_contract: String,
     pub token_in_h160: Address,
     pub token_in_symbol: String,
+    pub token_in_decimals: u8,
 }
 
 pub struct AmountOutCtx {
+    pub path: Vec<Address>,
     pub amount_out_list: Vec<U256>,
     pub token_out_contract: String,
     pub token_out_symbol: String,
     pub token_out_h160: Address,
+    pub token_out_decimals: u8,
 }
 
 pub struct GasCtx {
     pub gas_price: String,
-    pub gas_price_gwei: U256,
-    pub gas_limit: u64,
-    pub gas_limit_u256: U256,
+    // [None] if the gas price is 'auto'
+    pub gas_price_gwei: Option<U256>,
+    pub gas_price_multiplier_perc: Option<u32>,
+    pub min_gas_price_gwei: Option<U256>,
+    pub gas_limit_ctx: GasLimitCtx,
     pub tx_timeout_in_ms: i64,
     pub max_gas_price: Option<String>,
     pub max_gas_price_gwei: Option<U256>,
     pub perc_increase_gas_price: Option<u32>,
 }
 
+/// the 'auto' gas limit is estimated from the prepared tx right before it is sent
+#[derive(Clone)]
+pub struct GasLimitCtx {
+    // [None] if the gas limit is 'auto'
+    pub gas_limit_u256: Option<U256>,
+    pub gas_limit_margin_perc: u32,
+    pub max_gas_limit_u256: Option<U256>,
+}
+
 ////////////////////////////////////////////////////////////
 
 /// outputs for various methods
@@ -59,25 +87,38 @@ pub struct TokenInCTx {
     pub token_in_h160: Address,
     pub amount_of_token_in: String,
     pub amount_of_token_in_u256: U256,
-    pub slippage: u64,
+    pub token_in_decimals: u8,
 }
 
 pub struct TokenOutCTx {
+    pub path: Vec<Address>,
     pub amount_out_min_u256: U256,
     pub amount_out_max_u256: U256,
     pub token_out_symbol: String,
     pub token_out_contract: String,
     pub token_out_h160: Address,
+    pub token_out_decimals: u8,
     pub amount_out_min_in_human_readable_format: Quad,
     pub amount_out_max_in_human_readable_format: Quad,
     pub price_of_token_out_per_token_in_human_readable_format: Quad,
+    pub price_of_token_in_per_token_out_human_readable_format: Quad,
+    pub slippage: u64,
+    pub exact_output: Option<ExactOutputCTx>,
+}
+
+/// the input side of an exact output swap
+pub struct ExactOutputCTx {
+    pub amount_in_u256: U256,
+    pub amount_in_in_human_readable_format: Quad,
+    pub amount_in_max_u256: U256,
+    pub amount_in_max_in_human_readable_format: Quad,
 }
 
 pub struct GasTxCtx {
     pub gas_price: String,
     pub gas_price_gwei: U256,
-    pub gas_limit: u64,
-    pub gas_limit_u256: U256,
+    pub max_gas_price_gwei: Option<U256>,
+    pub gas_limit_ctx: GasLimitCtx,
     pub tx_timeout_in_ms: i64,
     pub tx_deadline_u256: U256,
     pub tx_deadline: i64,
@@ -89,55 +130,194 @@ pub struct TokenPairR {
     pub token_paired_time: Instant,
 }
 
+/// the swap tx of an earlier attempt that wasn't mined in time
+/// the next attempt replaces it with the same nonce instead of sending a new tx along with it
+pub struct PendingSwapCtx {
+    pub nonce: U256,
+
+    /// the pending tx followed by its replacements, only one of them can be mined
+    pub txs: Vec<PendingSwapTxCtx>,
+}
+
+pub struct PendingSwapTxCtx {
+    pub tx_hash: H256,
+    pub gas_price_gwei: U256,
+}
+
+/// the storage slots of the contracts overridden in a simulated 'eth_call'.
+/// it lets a swap be simulated from the account with the tokens it doesn't hold yet
+pub type StateOverride = HashMap<Address, AccountOverride>;
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct AccountOverride {
+    #[serde(rename = "stateDiff")]
+    pub state_diff: HashMap<H256, H256>,
+}
+
 ////////////////////////////////////////////////////////////
 
 impl<'a> QuantTradeHelper {
     // create token pair address
     // the method will wait until the token pair address is created if not found
-    pub async fn create_pair<T>(ctx: &TradeContext<T>) -> anyhow::Result<Address>
+    // the factory [PairCreated] event is subscribed to over the websocket provider,
+    // polling [getPair] is used only as a fallback when the subscription drops
+    pub async fn create_pair<T>(
+        ctx: &TradeContext<T>,
+        token_a_h160: Address,
+        token_b_h160: Address,
+    ) -> anyhow::Result<Address>
+    where
+        T: TradeSchemeVariant,
+    {
+        if let Some(pair_address) = Self::get_pair_address(ctx, token_a_h160, token_b_h160).await {
+            return Ok(pair_address);
+        }
+
+        match Self::wait_for_pair_created_event(ctx, token_a_h160, token_b_h160).await {
+            Ok(Some(pair_address)) => return Ok(pair_address),
+            Ok(None) => {
+                log::warn!("the PairCreated event subscription dropped, falling back to polling the pair address...");
+            }
+            Err(e) => {
+                log::error!("{:?}", e);
+                log::warn!("the PairCreated event subscription failed, falling back to polling the pair address...");
+            }
+        }
+
+        Ok(Self::poll_pair_address(ctx, token_a_h160, token_b_h160).await)
+    }
+
+    // fetch the token pair address from the factory
+    // returns [None] if the pair hasn't been created yet
+    pub async fn get_pair_address<T>(
+        ctx: &TradeContext<T>,
+        token_a_h160: Address,
+        token_b_h160: Address,
+    ) -> Option<Address>
     where
         T: TradeSchemeVariant,
     {
         let factory_get_pair_contract_call: ContractCall<
             SignerMiddleware<Provider<_>, LocalWallet>,
             Address,
-        > = ctx
+        > = ctx.contracts.factory.get_pair(token_a_h160, token_b_h160);
+
+        let pair_addr = factory_get_pair_contract_call.call().await;
+
+        match pair_addr {
+            Ok(pa) => {
+                log::debug!("\n",);
+                log::debug!("detected a pair address: {:?}", pa);
+
+                if pa.is_zero() {
+                    log::warn!("no liquidity pool found...");
+                    log::debug!("\n",);
+
+                    return None;
+                }
+
+                log::debug!("valid token pair address found: {:?}", pa);
+
+                Some(pa)
+            }
+            Err(e) => {
+                log::error!("an error occured while fetching the pair address: {:?}", e);
+
+                None
+            }
+        }
+    }
+
+    // wait for the factory to emit the [PairCreated] event for our token pair
+    // returns [None] if the subscription stream ends before the event is received
+    async fn wait_for_pair_created_event<T>(
+        ctx: &TradeContext<T>,
+        token_a_h160: Address,
+        token_b_h160: Address,
+    ) -> anyhow::Result<Option<Address>>
+    where
+        T: TradeSchemeVariant,
+    {
+        // the factory sorts the token pair by address before emitting [token0] and [token1]
+        let (token0_h160, token1_h160) = if token_a_h160 < token_b_h160 {
+            (token_a_h160, token_b_h160)
+        } else {
+            (token_b_h160, token_a_h160)
+        };
+
+        let pair_created_event = ctx
             .contracts
             .factory
-            .get_pair(ctx.feed.token_in_h160, ctx.feed.token_out_h160);
+            .pair_created_filter()
+            .topic1(H256::from(token0_h160))
+            .topic2(H256::from(token1_h160));
 
-        let mut pair_address: Address = Default::default();
+        log::debug!("subscribing to the PairCreated event...");
 
-        // loop until we fetch a valid pair address and we hit liquidity
-        'pair_address_loop: loop {
-            let pair_addr = factory_get_pair_contract_call.call().await;
+        let mut pair_created_stream = pair_created_event.subscribe().await?;
 
-            if let Ok(pa) = pair_addr {
-                let pa_str = format!("{:?}", pa);
+        // the pair could have been created before the subscription was established
+        if let Some(pair_address) = Self::get_pair_address(ctx, token_a_h160, token_b_h160).await {
+            return Ok(Some(pair_address));
+        }
 
-                log::debug!("\n",);
-                log::debug!("detected a pair address: {}", pa_str);
+        log::info!("waiting for the liquidity pool to be created...");
 
-                if pa_str.starts_with("0x0000000000000") {
-                    log::warn!("no liquidity pool found, retrying...");
+        while let Some(pair_created) = pair_created_stream.next().await {
+            match pair_created {
+                Ok(p) => {
                     log::debug!("\n",);
-                } else {
-                    pair_address = pa;
+                    log::debug!("received a PairCreated event: {:?}", p);
+
+                    if p.pair.is_zero() {
+                        continue;
+                    }
 
-                    log::debug!("valid token pair address found: {:?}", pair_address);
+                    log::debug!("valid token pair address found: {:?}", p.pair);
 
-                    break 'pair_address_loop;
+                    return Ok(Some(p.pair));
                 }
-            };
+                Err(e) => {
+                    log::error!(
+                        "an error occured while decoding the PairCreated event: {:?}",
+                        e
+                    );
+                }
+            }
         }
 
-        Ok(pair_address)
+        Ok(None)
+    }
+
+    // poll the factory until a valid pair address is found
+    async fn poll_pair_address<T>(
+        ctx: &TradeContext<T>,
+        token_a_h160: Address,
+        token_b_h160: Address,
+    ) -> Address
+    where
+        T: TradeSchemeVariant,
+    {
+        // loop until we fetch a valid pair address
+        loop {
+            if let Some(pair_address) =
+                Self::get_pair_address(ctx, token_a_h160, token_b_h160).await
+            {
+                return pair_address;
+            }
+
+            log::warn!("retrying...");
+
+            tokio_sleep(DefaultValues::PAIR_ADDRESS_POLLING_INTERVAL_IN_MS).await;
+        }
     }
 
     // create token pair value
     // the method will wait until the minimum required liquidity is found
+    // [liquidity_token_decimals] is the 'decimals' of the [liquidity_token_erc20]
     pub async fn token_pair_value(
-        contracts: &QuantContracts,
+        liquidity_token_erc20: &Erc20Contract,
+        liquidity_token_decimals: u8,
         pair_address: Address,
         min_liquidity_required: &Option<U256>,
     ) -> anyhow::Result<TokenPairR> {
@@ -146,7 +326,7 @@ impl<'a> QuantTradeHelper {
         let erc20_balance_of_contract_call: ContractCall<
             SignerMiddleware<Provider<_>, LocalWallet>,
             U256,
-        > = contracts.token_in_erc20.balance_of(pair_address);
+        > = liquidity_token_erc20.balance_of(pair_address);
 
         let mut check_min_liquidity_hit = false;
         let mut paired_tokens_value: U256 = Default::default();
@@ -164,7 +344,7 @@ impl<'a> QuantTradeHelper {
                 Ok(token_value) => {
                     paired_tokens_value = token_value;
                     token_value_to_eth_to_human_display =
-                        ether_to_human_display(paired_tokens_value);
+                        units_to_human_display(paired_tokens_value, liquidity_token_decimals);
 
                     log::debug!("\n");
                     log::debug!("erc20 balance: {}", token_value);
@@ -214,10 +394,282 @@ impl<'a> QuantTradeHelper {
         })
     }
 
+    // the first route whose token pairs have all been created
+    async fn available_route<T>(ctx: &TradeContext<T>) -> Option<Vec<Address>>
+    where
+        T: TradeSchemeVariant,
+    {
+        'routes_loop: for route in &ctx.routes {
+            for pair in route.windows(2) {
+                if Self::get_pair_address(ctx, pair[0], pair[1])
+                    .await
+                    .is_none()
+                {
+                    continue 'routes_loop;
+                }
+            }
+
+            log::debug!("picked the route for the token pair: {:?}", route);
+
+            return Some(route.to_owned());
+        }
+
+        None
+    }
+
+    // pick the route whose token pairs have been created
+    // before the launch none of the routes are available yet, so the final pair of every route is waited for
+    // since the liquidity of [token_out] could be added against any of the intermediary tokens
+    pub async fn pair_route<T>(ctx: &TradeContext<T>) -> anyhow::Result<Vec<Address>>
+    where
+        T: TradeSchemeVariant,
+    {
+        // the pair of a single route is waited for by [create_pair]
+        if ctx.routes.len() == 1 {
+            return Ok(ctx.routes[0].to_owned());
+        }
+
+        if let Some(route) = Self::available_route(ctx).await {
+            return Ok(route);
+        }
+
+        match Self::wait_for_route_pair_created_event(ctx).await {
+            Ok(Some(route)) => return Ok(route),
+            Ok(None) => {
+                log::warn!("the PairCreated event subscription dropped, falling back to polling the routes...");
+            }
+            Err(e) => {
+                log::error!("{:?}", e);
+                log::warn!("the PairCreated event subscription failed, falling back to polling the routes...");
+            }
+        }
+
+        Ok(Self::poll_available_route(ctx).await)
+    }
+
+    // every [PairCreated] event of the factory is subscribed to since [token_out] could be paired against any token of the routes
+    // the routes are checked again on every new pair
+    // returns [None] if the subscription stream ends before a route is available
+    async fn wait_for_route_pair_created_event<T>(
+        ctx: &TradeContext<T>,
+    ) -> anyhow::Result<Option<Vec<Address>>>
+    where
+        T: TradeSchemeVariant,
+    {
+        let pair_created_event = ctx.contracts.factory.pair_created_filter();
+
+        log::debug!("subscribing to the PairCreated event of every route...");
+
+        let mut pair_created_stream = pair_created_event.subscribe().await?;
+
+        // the pairs could have been created before the subscription was established
+        if let Some(route) = Self::available_route(ctx).await {
+            return Ok(Some(route));
+        }
+
+        log::info!("waiting for the liquidity pool of any of the routes to be created...");
+
+        while let Some(pair_created) = pair_created_stream.next().await {
+            match pair_created {
+                Ok(p) => {
+                    if p.pair.is_zero() {
+                        continue;
+                    }
+
+                    log::debug!("received a PairCreated event: {:?}", p);
+
+                    if let Some(route) = Self::available_route(ctx).await {
+                        return Ok(Some(route));
+                    }
+                }
+                Err(e) => {
+                    log::error!(
+                        "an error occured while decoding the PairCreated event: {:?}",
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    // poll the factory until the token pairs of any of the routes are created
+    async fn poll_available_route<T>(ctx: &TradeContext<T>) -> Vec<Address>
+    where
+        T: TradeSchemeVariant,
+    {
+        loop {
+            if let Some(route) = Self::available_route(ctx).await {
+                return route;
+            }
+
+            log::warn!("retrying...");
+
+            tokio_sleep(DefaultValues::PAIR_ADDRESS_POLLING_INTERVAL_IN_MS).await;
+        }
+    }
+
+    // fetch the amounts out for every route and pick the route with the best output
+    pub async fn amount_out_list<T>(
+        ctx: &TradeContext<T>,
+        amount_of_token_in_u256: U256,
+    ) -> anyhow::Result<(Vec<Address>, Vec<U256>)>
+    where
+        T: TradeSchemeVariant,
+    {
+        // a single route doesn't need to be compared
+        if ctx.routes.len() == 1 {
+            let route = ctx.routes[0].to_owned();
+
+            let amount_out_list_contract_call: ContractCall<
+                SignerMiddleware<Provider<_>, LocalWallet>,
+                Vec<U256>,
+            > = ctx
+                .contracts
+                .router
+                .get_amounts_out(amount_of_token_in_u256, route.to_owned());
+
+            let amount_out_list = amount_out_list_contract_call.call().await?;
+
+            return Ok((route, amount_out_list));
+        }
+
+        let mut best_route: Option<(Vec<Address>, Vec<U256>)> = None;
+
+        for route in &ctx.routes {
+            let amount_out_list_contract_call: ContractCall<
+                SignerMiddleware<Provider<_>, LocalWallet>,
+                Vec<U256>,
+            > = ctx
+                .contracts
+                .router
+                .get_amounts_out(amount_of_token_in_u256, route.to_owned());
+
+            let amount_out_list = match amount_out_list_contract_call.call().await {
+                Ok(d) => d,
+                Err(e) => {
+                    log::debug!("the route {:?} is unavailable: {:?}", route, e);
+
+                    continue;
+                }
+            };
+
+            let amount_out_u256 = amount_out_list.last().copied().unwrap_or_default();
+
+            log::debug!(
+                "the route {:?} returns {} tokens",
+                route,
+                units_to_human_display(amount_out_u256, ctx.contracts.token_out_decimals)
+            );
+
+            let is_better_route = match &best_route {
+                None => true,
+                Some((_, b)) => amount_out_u256 > b.last().copied().unwrap_or_default(),
+            };
+
+            if is_better_route {
+                best_route = Some((route.to_owned(), amount_out_list));
+            }
+        }
+
+        match best_route {
+            None => Err(TradingError::Route("none of the swap routes are available").into()),
+            Some(d) => {
+                log::debug!("picked the best route: {:?}", d.0);
+
+                Ok(d)
+            }
+        }
+    }
+
+    // fetch the amounts in for every route and pick the route which requires the least input
+    pub async fn amount_in_list<T>(
+        ctx: &TradeContext<T>,
+        amount_of_token_out_u256: U256,
+    ) -> anyhow::Result<(Vec<Address>, Vec<U256>)>
+    where
+        T: TradeSchemeVariant,
+    {
+        let mut best_route: Option<(Vec<Address>, Vec<U256>)> = None;
+
+        for route in &ctx.routes {
+            let amount_in_list_contract_call: ContractCall<
+                SignerMiddleware<Provider<_>, LocalWallet>,
+                Vec<U256>,
+            > = ctx
+                .contracts
+                .router
+                .get_amounts_in(amount_of_token_out_u256, route.to_owned());
+
+            let amount_in_list = match amount_in_list_contract_call.call().await {
+                Ok(d) => d,
+                Err(e) => {
+                    // a single route has nothing to compare against
+                    if ctx.routes.len() == 1 {
+                        return Err(e.into());
+                    }
+
+                    log::debug!("the route {:?} is unavailable: {:?}", route, e);
+
+                    continue;
+                }
+            };
+
+            let amount_in_u256 = amount_in_list.first().copied().unwrap_or_default();
+
+            let is_better_route = match &best_route {
+                None => true,
+                Some((_, b)) => amount_in_u256 < b.first().copied().unwrap_or_default(),
+            };
+
+            if is_better_route {
+                best_route = Some((route.to_owned(), amount_in_list));
+            }
+        }
+
+        match best_route {
+            None => Err(TradingError::Route("none of the swap routes are available").into()),
+            Some(d) => {
+                log::debug!("picked the best route: {:?}", d.0);
+
+                Ok(d)
+            }
+        }
+    }
+
+    // compute the amount of token out for [amount_in] against the pair reserves
+    // this mirrors the 'getAmountOut' method of the router
+    pub fn amount_out_from_reserves(amount_in: U256, reserve_in: U256, reserve_out: U256) -> U256 {
+        if amount_in.is_zero() || reserve_in.is_zero() || reserve_out.is_zero() {
+            return U256::zero();
+        }
+
+        let amount_in_with_fee = amount_in.mul(DefaultValues::ROUTER_SWAP_FEE_NUMERATOR);
+        let numerator = amount_in_with_fee.mul(reserve_out);
+        let denominator = reserve_in
+            .mul(DefaultValues::ROUTER_SWAP_FEE_DENOMINATOR)
+            .add(amount_in_with_fee);
+
+        numerator.div(denominator)
+    }
+
     pub(crate) async fn get_token_balance_in_account(
         q: &Quant,
         contracts: &QuantContracts,
     ) -> anyhow::Result<(Quad, U256)> {
+        // the native coin is held in the account instead of the token contract
+        if contracts.token_in_erc20.address() == q.variables.native_token_h160 {
+            let account_balance_u256 =
+                get_account_balance(&q.middleware.client, &q.variables.account_address_h160)
+                    .await?;
+
+            return Ok((
+                ether_to_human_display(account_balance_u256),
+                account_balance_u256,
+            ));
+        }
+
         let erc20_balance_of_in_account_contract_call: ContractCall<
             SignerMiddleware<Provider<_>, LocalWallet>,
             U256,
@@ -229,7 +681,7 @@ impl<'a> QuantTradeHelper {
             erc20_balance_of_in_account_contract_call.call().await?;
 
         let erc20_balance_in_account_human_readable_format =
-            ether_to_human_display(erc20_balance_in_account_u256);
+            units_to_human_display(erc20_balance_in_account_u256, contracts.token_in_decimals);
 
         Ok((
             erc20_balance_in_account_human_readable_format,
@@ -237,11 +689,178 @@ impl<'a> QuantTradeHelper {
         ))
     }
 
+    // sum of the [token_h160] tokens transferred to any of the [recipients_h160] in the transaction
+    // the amount is decoded from the ERC20 'Transfer' events of the tx receipt
+    pub fn tokens_received_from_receipt(
+        tx_receipt: &TransactionReceipt,
+        token_h160: Address,
+        recipients_h160: &[Address],
+    ) -> U256 {
+        let mut tokens_received_u256 = U256::zero();
+
+        for l in &tx_receipt.logs {
+            if l.address != token_h160 {
+                continue;
+            }
+
+            let raw_log = RawLog {
+                topics: l.topics.clone(),
+                data: l.data.to_vec(),
+            };
+
+            if let Ok(transfer) = Erc20TransferFilter::decode_log(&raw_log) {
+                if recipients_h160.contains(&transfer.to) {
+                    tokens_received_u256 = tokens_received_u256.add(transfer.value);
+                }
+            }
+        }
+
+        tokens_received_u256
+    }
+
+    // sum of the [token_h160] tokens transferred out of any of the [senders_h160] in the transaction
+    // the native coin is wrapped by the router, so it is sent out of the router instead of the account
+    pub fn tokens_sent_from_receipt(
+        tx_receipt: &TransactionReceipt,
+        token_h160: Address,
+        senders_h160: &[Address],
+    ) -> U256 {
+        let mut tokens_sent_u256 = U256::zero();
+
+        for l in &tx_receipt.logs {
+            if l.address != token_h160 {
+                continue;
+            }
+
+            let raw_log = RawLog {
+                topics: l.topics.clone(),
+                data: l.data.to_vec(),
+            };
+
+            if let Ok(transfer) = Erc20TransferFilter::decode_log(&raw_log) {
+                if senders_h160.contains(&transfer.from) {
+                    tokens_sent_u256 = tokens_sent_u256.add(transfer.value);
+                }
+            }
+        }
+
+        tokens_sent_u256
+    }
+
+    // the spent [token_in] and the gas fees of a mined swap are recorded in the ledger of the network
+    // the trade isn't failed here since the swap has already been mined
+    fn record_swap_in_ledger(
+        quant: &Quant,
+        token_in_ctx: &TokenInCTx,
+        gas_tx_ctx: &GasTxCtx,
+        tx_receipt: &TransactionReceipt,
+    ) {
+        let amount_of_token_in_u256 = Self::tokens_sent_from_receipt(
+            tx_receipt,
+            token_in_ctx.token_in_h160,
+            &[
+                quant.variables.account_address_h160,
+                quant.variables.router_in_h160,
+            ],
+        );
+        let gas_fees_u256 = tx_receipt
+            .gas_used
+            .unwrap_or_default()
+            .mul(gas_tx_ctx.gas_price_gwei);
+
+        let entry = LedgerEntry {
+            timestamp: chrono::offset::Utc::now().timestamp(),
+            account_h160: quant.variables.account_address_h160,
+            tx_hash: tx_receipt.transaction_hash,
+            token_in_h160: token_in_ctx.token_in_h160,
+            amount_of_token_in_u256,
+            gas_fees_u256,
+        };
+
+        Self::record_in_ledger(quant, entry);
+    }
+
+    // the gas fees of a mined tx which doesn't spend any [token_in], e.g. an approval or a token transfer
+    // a reverted tx is recorded as well since its gas fees are spent anyway
+    pub fn record_gas_in_ledger(
+        quant: &Quant,
+        token_h160: Address,
+        gas_price_gwei: U256,
+        tx_receipt: &TransactionReceipt,
+    ) {
+        let gas_fees_u256 = tx_receipt.gas_used.unwrap_or_default().mul(gas_price_gwei);
+
+        let entry = LedgerEntry {
+            timestamp: chrono::offset::Utc::now().timestamp(),
+            account_h160: quant.variables.account_address_h160,
+            tx_hash: tx_receipt.transaction_hash,
+            token_in_h160: token_h160,
+            amount_of_token_in_u256: U256::zero(),
+            gas_fees_u256,
+        };
+
+        Self::record_in_ledger(quant, entry);
+    }
+
+    fn record_in_ledger(quant: &Quant, entry: LedgerEntry) {
+        // the trades are carried out at the same time, only one of them writes to the ledger file at a time
+        let _ledger_guard = quant.ledger_lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        let file_path = Ledger::file_path(&quant.variables.network_name);
+        if let Err(e) = Ledger::record(&file_path, entry) {
+            log::error!("unable to record the tx in the ledger: {:?}", e);
+        }
+    }
+
+    // the fixed gas limit, or the 'auto' gas limit estimated from the prepared tx
+    // the estimation reverts while the liquidity isn't live yet, [max_gas_limit_u256] is used instead
+    async fn tx_gas_limit(
+        q: &Quant,
+        tx: &TransactionRequest,
+        gas_limit_ctx: &GasLimitCtx,
+    ) -> anyhow::Result<U256> {
+        if let Some(d) = gas_limit_ctx.gas_limit_u256 {
+            return Ok(d);
+        }
+
+        let tx = tx.clone().from(q.variables.account_address_h160);
+
+        let estimated_gas_u256 = match q.middleware.client.estimate_gas(&tx).await {
+            Ok(d) => d,
+            Err(e) => {
+                log::debug!("unable to estimate the gas of the tx: {:?}", e);
+
+                return match gas_limit_ctx.max_gas_limit_u256 {
+                    None => Err(e.into()),
+                    Some(m) => {
+                        log::warn!("unable to estimate the gas of the tx, falling back to the max gas limit: {}", m);
+
+                        Ok(m)
+                    }
+                };
+            }
+        };
+
+        let gas_limit_u256 = gas_limit_with_margin(
+            estimated_gas_u256,
+            gas_limit_ctx.gas_limit_margin_perc,
+            gas_limit_ctx.max_gas_limit_u256,
+        );
+
+        log::info!(
+            "auto gas limit: {} (estimated gas: {})",
+            gas_limit_u256,
+            estimated_gas_u256
+        );
+
+        Ok(gas_limit_u256)
+    }
+
     async fn start_token_approval(
         ecr20_contract: &Erc20Contract,
         q: &Quant,
         approve_gas_price_gwei: U256,
-        approve_gas_limit_u256: U256,
+        approve_gas_limit_ctx: &GasLimitCtx,
     ) -> anyhow::Result<()> {
         log::debug!("approving the token...");
 
@@ -254,14 +873,20 @@ impl<'a> QuantTradeHelper {
                 DefaultValues::TOKEN_ALLOWANCE_MAX_AMOUNT,
             );
 
+            let approve_gas_limit_u256 =
+                Self::tx_gas_limit(q, &approve_token_contract_call.tx, approve_gas_limit_ctx)
+                    .await?;
+
             let approve_token_contract_call = approve_token_contract_call
                 .gas(approve_gas_limit_u256)
                 .gas_price(approve_gas_price_gwei);
 
+            let nonce = q.middleware.next_nonce().await?;
+
             let approve_token_pending_tx_res = q
                 .middleware
                 .client
-                .send_transaction(approve_token_contract_call.tx, None)
+                .send_transaction(approve_token_contract_call.tx.nonce(nonce), None)
                 .await;
 
             match approve_token_pending_tx_res {
@@ -274,6 +899,15 @@ impl<'a> QuantTradeHelper {
 
                     let tx_receipt_call = &t.await;
 
+                    if let Ok(Some(r)) = tx_receipt_call {
+                        Self::record_gas_in_ledger(
+                            q,
+                            ecr20_contract.address(),
+                            approve_gas_price_gwei,
+                            r,
+                        );
+                    }
+
                     match tx_receipt_call {
                         Ok(tx_receipt) => match tx_receipt {
                             Some(r) => match r.status {
@@ -306,6 +940,8 @@ impl<'a> QuantTradeHelper {
                 Err(e) => {
                     log::error!("{:?}", e);
                     log::warn!("approve token tx call was unsuccessful, retrying...");
+
+                    q.middleware.reset_nonce().await;
                 }
             }
         }
@@ -318,11 +954,11 @@ impl<'a> QuantTradeHelper {
         contracts: &QuantContracts,
         q: &Quant,
         approve_gas_price_gwei_option: Option<U256>,
-        approve_gas_limit_u256_option: Option<U256>,
+        approve_gas_limit_ctx_option: Option<GasLimitCtx>,
         token_in_symbol: String,
     ) -> anyhow::Result<Option<()>> {
         let approve_gas_price_gwei: U256;
-        let approve_gas_limit_u256: U256;
+        let approve_gas_limit_ctx: GasLimitCtx;
 
         if let Some(a) = approve_gas_price_gwei_option {
             approve_gas_price_gwei = a;
@@ -330,12 +966,22 @@ impl<'a> QuantTradeHelper {
             return Ok(None);
         }
 
-        if let Some(a) = approve_gas_limit_u256_option {
-            approve_gas_limit_u256 = a;
+        if let Some(a) = approve_gas_limit_ctx_option {
+            approve_gas_limit_ctx = a;
         } else {
             return Ok(None);
         }
 
+        // the native coin is sent as the value of the swap and doesn't need an approval
+        if contracts.token_in_erc20.address() == q.variables.native_token_h160 {
+            log::debug!(
+                "{} is the native token, skipping the approval...",
+                token_in_symbol
+            );
+
+            return Ok(None);
+        }
+
         log::debug!(
             "checking the approval status of {} token...",
             token_in_symbol
@@ -364,7 +1010,7 @@ impl<'a> QuantTradeHelper {
             &contracts.token_in_erc20,
             q,
             approve_gas_price_gwei,
-            approve_gas_limit_u256,
+            &approve_gas_limit_ctx,
         )
         .await?;
 
@@ -379,59 +1025,237 @@ impl<'a> QuantTradeHelper {
         let t = TokenInCTx {
             amount_of_token_in_u256,
             amount_of_token_in,
-            slippage: amount_in_ctx.slippage,
             token_in_contract: amount_in_ctx.token_in_contract.to_owned(),
             token_in_symbol: amount_in_ctx.token_in_symbol.to_owned(),
             token_in_h160: amount_in_ctx.token_in_h160,
+            token_in_decimals: amount_in_ctx.token_in_decimals,
         };
 
         Ok(t)
     }
 
+    // the slippage of the current attempt
+    // [slippage] is increased by [perc_increase_slippage] on every retry attempt and it is capped at [max_slippage]
+    fn slippage(amount_in_ctx: &AmountInCtx, trade_attempt_count: u64) -> u64 {
+        let slippage = amount_in_ctx.slippage;
+        let trade_retry_attempt_count = trade_attempt_count.saturating_sub(1);
+
+        if trade_retry_attempt_count == 0 {
+            return slippage;
+        }
+
+        let increase_in_perc = match amount_in_ctx.perc_increase_slippage {
+            None => return slippage,
+            Some(d) => d,
+        };
+
+        let increase_by_factor = increase_in_perc as u64 * trade_retry_attempt_count;
+        let slippage_new = slippage + slippage * increase_by_factor / 100;
+
+        let max_slippage = amount_in_ctx.max_slippage.unwrap_or(100) as u64;
+
+        if slippage_new > max_slippage {
+            log::warn!("the newly computed slippage ({}%) has crossed the max allowed slippage ({}%), will continue with the max allowed slippage...", slippage_new, max_slippage);
+
+            return max_slippage;
+        }
+
+        log::info!("increasing the slippage to: {}%", slippage_new);
+
+        slippage_new
+    }
+
     pub fn token_out(
         amount_in_ctx: &AmountInCtx,
         amount_out_ctx: &AmountOutCtx,
+        trade_attempt_count: u64,
     ) -> anyhow::Result<TokenOutCTx> {
-        let amount_out_max_u256 = amount_out_ctx.amount_out_list[1];
-        let amount_out_max_in_human_readable_format = ether_to_human_display(amount_out_max_u256);
+        let slippage = Self::slippage(amount_in_ctx, trade_attempt_count);
+
+        // the last item of the [amount_out_list] is the amount of [token_out] at the end of the path
+        let amount_out_max_u256 = amount_out_ctx
+            .amount_out_list
+            .last()
+            .copied()
+            .unwrap_or_default();
+        let amount_out_max_in_human_readable_format =
+            units_to_human_display(amount_out_max_u256, amount_out_ctx.token_out_decimals);
 
         // the correction is done here because Quad library doesn't accept decimals as input for [amount_out_min]
         // we first strip the decimal points out of it to convert [amount_out_min] into units of ethers
-        let amount_out_min_correction_u256 = amount_out_max_u256.sub(percentage_of_u256(
-            amount_out_max_u256,
-            amount_in_ctx.slippage,
-        ));
-        let amount_out_min_in_human_readable_format =
-            ether_to_human_display(amount_out_min_correction_u256);
+        let amount_out_min_correction_u256 =
+            amount_out_max_u256.sub(percentage_of_u256(amount_out_max_u256, slippage));
+        let amount_out_min_in_human_readable_format = units_to_human_display(
+            amount_out_min_correction_u256,
+            amount_out_ctx.token_out_decimals,
+        );
 
         let price_of_token_out_per_token_in_human_readable_format = divide_into_f256(
             &amount_in_ctx.amount_of_token_in,
             &amount_out_max_in_human_readable_format,
         );
+        let price_of_token_in_per_token_out_human_readable_format = divide_into_f256(
+            &amount_out_max_in_human_readable_format,
+            &amount_in_ctx.amount_of_token_in,
+        );
 
         let t = TokenOutCTx {
+            path: amount_out_ctx.path.to_owned(),
             amount_out_min_in_human_readable_format,
             amount_out_min_u256: amount_out_min_correction_u256,
             amount_out_max_u256,
             amount_out_max_in_human_readable_format,
             token_out_symbol: amount_out_ctx.token_out_symbol.to_owned(),
             price_of_token_out_per_token_in_human_readable_format,
+            price_of_token_in_per_token_out_human_readable_format,
+            token_out_h160: amount_out_ctx.token_out_h160,
+            token_out_decimals: amount_out_ctx.token_out_decimals,
+            token_out_contract: amount_out_ctx.token_out_contract.to_owned(),
+            slippage,
+            exact_output: None,
+        };
+
+        Ok(t)
+    }
+
+    // the [amount_out_list] of an exact output swap is fetched using 'getAmountsIn'
+    // the output is fixed, so the slippage is applied to the input side
+    // [amount_of_token_in] is the maximum amount of token in allowed to be spent
+    pub fn token_out_for_exact_output(
+        amount_in_ctx: &AmountInCtx,
+        amount_out_ctx: &AmountOutCtx,
+        trade_attempt_count: u64,
+    ) -> anyhow::Result<TokenOutCTx> {
+        let slippage = Self::slippage(amount_in_ctx, trade_attempt_count);
+
+        let amount_in_u256 = amount_out_ctx
+            .amount_out_list
+            .first()
+            .copied()
+            .unwrap_or_default();
+        let amount_out_u256 = amount_out_ctx
+            .amount_out_list
+            .last()
+            .copied()
+            .unwrap_or_default();
+
+        let amount_in_max_u256 = min!(
+            amount_in_u256.add(percentage_of_u256(amount_in_u256, slippage)),
+            amount_in_ctx.amount_of_token_in_u256
+        );
+
+        let amount_in_in_human_readable_format =
+            units_to_human_display(amount_in_u256, amount_in_ctx.token_in_decimals);
+        let amount_out_in_human_readable_format =
+            units_to_human_display(amount_out_u256, amount_out_ctx.token_out_decimals);
+
+        let price_of_token_out_per_token_in_human_readable_format = divide_into_f256(
+            &amount_in_in_human_readable_format,
+            &amount_out_in_human_readable_format,
+        );
+        let price_of_token_in_per_token_out_human_readable_format = divide_into_f256(
+            &amount_out_in_human_readable_format,
+            &amount_in_in_human_readable_format,
+        );
+
+        let e = ExactOutputCTx {
+            amount_in_u256,
+            amount_in_in_human_readable_format,
+            amount_in_max_u256,
+            amount_in_max_in_human_readable_format: units_to_human_display(
+                amount_in_max_u256,
+                amount_in_ctx.token_in_decimals,
+            ),
+        };
+
+        let t = TokenOutCTx {
+            path: amount_out_ctx.path.to_owned(),
+            amount_out_min_in_human_readable_format: amount_out_in_human_readable_format,
+            amount_out_min_u256: amount_out_u256,
+            amount_out_max_u256: amount_out_u256,
+            amount_out_max_in_human_readable_format: amount_out_in_human_readable_format,
+            token_out_symbol: amount_out_ctx.token_out_symbol.to_owned(),
+            price_of_token_out_per_token_in_human_readable_format,
+            price_of_token_in_per_token_out_human_readable_format,
             token_out_h160: amount_out_ctx.token_out_h160,
+            token_out_decimals: amount_out_ctx.token_out_decimals,
             token_out_contract: amount_out_ctx.token_out_contract.to_owned(),
+            slippage,
+            exact_output: Some(e),
         };
 
         Ok(t)
     }
 
-    pub fn gas(gas_ctx: &GasCtx, trade_attempt_count: u64) -> anyhow::Result<GasTxCtx> {
+    // the 'auto' gas price is computed from the network gas price on every attempt
+    async fn auto_gas_price(q: &Quant, gas_ctx: &GasCtx) -> anyhow::Result<(String, U256)> {
+        let network_gas_price_gwei = get_network_gas_price(&q.middleware.client).await?;
+
+        let (gas_price, gas_price_gwei) = auto_gas_price(
+            network_gas_price_gwei,
+            gas_ctx.gas_price_multiplier_perc,
+            gas_ctx.min_gas_price_gwei,
+            gas_ctx.max_gas_price_gwei,
+        );
+
+        log::info!(
+            "auto gas price: {} gwei (network gas price: {} gwei)",
+            gas_price,
+            gwei_to_human_display(network_gas_price_gwei)
+        );
+
+        Ok((gas_price.to_string(), gas_price_gwei))
+    }
+
+    pub fn gas_limit_ctx<T>(scheme: &T) -> anyhow::Result<GasLimitCtx>
+    where
+        T: TradeSchemeVariant,
+    {
+        let g = GasLimitCtx {
+            gas_limit_u256: scheme.gas_limit_u256()?,
+            gas_limit_margin_perc: scheme.gas_limit_margin_perc(),
+            max_gas_limit_u256: scheme.max_gas_limit_u256()?,
+        };
+
+        Ok(g)
+    }
+
+    // [None] if the approve gas limit is missing
+    pub fn approve_gas_limit_ctx<T>(scheme: &T) -> anyhow::Result<Option<GasLimitCtx>>
+    where
+        T: TradeSchemeVariant,
+    {
+        let gas_limit_u256 = scheme.approve_gas_limit_u256()?;
+
+        if gas_limit_u256.is_none() && !scheme.is_auto_approve_gas_limit_active() {
+            return Ok(None);
+        }
+
+        let g = GasLimitCtx {
+            gas_limit_u256,
+            gas_limit_margin_perc: scheme.gas_limit_margin_perc(),
+            max_gas_limit_u256: scheme.max_approve_gas_limit_u256()?,
+        };
+
+        Ok(Some(g))
+    }
+
+    pub async fn gas(
+        q: &Quant,
+        gas_ctx: &GasCtx,
+        trade_attempt_count: u64,
+    ) -> anyhow::Result<GasTxCtx> {
         let tx_timeout_in_ms = gas_ctx.tx_timeout_in_ms;
+        // the router compares the deadline with 'block.timestamp' which is in seconds
         let tx_deadline = chrono::offset::Utc::now()
             .add(ChronoDuration::milliseconds(tx_timeout_in_ms))
-            .timestamp_millis();
+            .timestamp();
         let tx_deadline_u256 = U256::from(tx_deadline);
 
-        let mut gas_price = gas_ctx.gas_price.to_owned();
-        let mut gas_price_gwei = gas_ctx.gas_price_gwei;
+        let (mut gas_price, mut gas_price_gwei) = match gas_ctx.gas_price_gwei {
+            None => Self::auto_gas_price(q, gas_ctx).await?,
+            Some(d) => (gas_ctx.gas_price.to_owned(), d),
+        };
         let trade_retry_attempt_count = max!(trade_attempt_count - 1, 0);
 
         let max_gas_price_str = match &gas_ctx.max_gas_price {
@@ -483,8 +1307,8 @@ impl<'a> QuantTradeHelper {
         let g = GasTxCtx {
             gas_price,
             gas_price_gwei,
-            gas_limit: gas_ctx.gas_limit,
-            gas_limit_u256: gas_ctx.gas_limit_u256,
+            max_gas_price_gwei: gas_ctx.max_gas_price_gwei,
+            gas_limit_ctx: gas_ctx.gas_limit_ctx.clone(),
             tx_timeout_in_ms,
             tx_deadline_u256,
             tx_deadline,
@@ -511,11 +1335,13 @@ impl<'a> QuantTradeHelper {
             token_out_ctx.token_out_symbol,
             token_out_ctx.token_out_contract
         );
-        log::info!(
-            "Amount of token in: {}({})",
-            token_in_ctx.amount_of_token_in,
-            token_in_ctx.token_in_symbol
-        );
+        if token_out_ctx.exact_output.is_none() {
+            log::info!(
+                "Amount of token in: {}({})",
+                token_in_ctx.amount_of_token_in,
+                token_in_ctx.token_in_symbol
+            );
+        }
 
         log::info!(
             "Price: {:.14} {} per {}",
@@ -524,24 +1350,55 @@ impl<'a> QuantTradeHelper {
             token_out_ctx.token_out_symbol,
         );
 
-        log::info!(
-            "Amount of {} tokens you will get for {} {}s: {}",
-            token_out_ctx.token_out_symbol,
-            token_in_ctx.amount_of_token_in,
-            token_in_ctx.token_in_symbol,
-            token_out_ctx.amount_out_max_in_human_readable_format,
-        );
+        match &token_out_ctx.exact_output {
+            None => {
+                log::info!(
+                    "Amount of {} tokens you will get for {} {}s: {}",
+                    token_out_ctx.token_out_symbol,
+                    token_in_ctx.amount_of_token_in,
+                    token_in_ctx.token_in_symbol,
+                    token_out_ctx.amount_out_max_in_human_readable_format,
+                );
 
-        log::info!(
-            "Minimum amount of {} you will get: {}",
-            token_out_ctx.token_out_symbol,
-            token_out_ctx.amount_out_min_in_human_readable_format
-        );
+                log::info!(
+                    "Minimum amount of {} you will get: {}",
+                    token_out_ctx.token_out_symbol,
+                    token_out_ctx.amount_out_min_in_human_readable_format
+                );
+            }
+            Some(e) => {
+                log::info!(
+                    "Exact amount of {} tokens you will get: {}",
+                    token_out_ctx.token_out_symbol,
+                    token_out_ctx.amount_out_max_in_human_readable_format,
+                );
+
+                log::info!(
+                    "Estimated amount of {} you will spend: {}",
+                    token_in_ctx.token_in_symbol,
+                    e.amount_in_in_human_readable_format
+                );
 
-        log::info!("Slippage Tolerance: {}%", token_in_ctx.slippage);
+                log::info!(
+                    "Maximum amount of {} you will spend: {}",
+                    token_in_ctx.token_in_symbol,
+                    e.amount_in_max_in_human_readable_format
+                );
+            }
+        }
+
+        if token_out_ctx.path.len() > 2 {
+            log::info!("Path: {:?}", token_out_ctx.path);
+        }
+
+        log::info!("Slippage Tolerance: {}%", token_out_ctx.slippage);
         log::info!("Account address: {}", quant.variables.account_address);
         log::info!("Gas Price (GWEI): {}", gas_tx_ctx.gas_price);
-        log::info!("Gas Limit: {}", gas_tx_ctx.gas_limit);
+
+        match gas_tx_ctx.gas_limit_ctx.gas_limit_u256 {
+            None => log::info!("Gas Limit: {}", DefaultValues::AUTO_KEYWORD),
+            Some(d) => log::info!("Gas Limit: {}", d),
+        }
     }
 
     pub fn swap_deflationary_tokens_contract_call<T>(
@@ -551,86 +1408,512 @@ impl<'a> QuantTradeHelper {
         gas_tx_ctx: &GasTxCtx,
         quant: &Quant,
     ) -> ContractCall<SignerMiddleware<Provider<Ws>, LocalWallet>, Vec<U256>>
+    where
+        T: TradeSchemeVariant,
+    {
+        ctx.contracts
+            .router
+            .swap_exact_tokens_for_tokens_supporting_fee_on_transfer_tokens(
+                token_in_ctx.amount_of_token_in_u256,
+                token_out_ctx.amount_out_min_u256,
+                token_out_ctx.path.to_owned(),
+                quant.variables.account_address_h160,
+                gas_tx_ctx.tx_deadline_u256,
+            )
+    }
+
+    pub fn swap_non_deflationary_tokens_contract_call<T>(
+        ctx: &TradeContext<T>,
+        token_in_ctx: &TokenInCTx,
+        token_out_ctx: &TokenOutCTx,
+        gas_tx_ctx: &GasTxCtx,
+        quant: &Quant,
+    ) -> ContractCall<SignerMiddleware<Provider<Ws>, LocalWallet>, Vec<U256>>
     where
         T: TradeSchemeVariant,
     {
         ctx.contracts.router.swap_exact_tokens_for_tokens(
             token_in_ctx.amount_of_token_in_u256,
             token_out_ctx.amount_out_min_u256,
-            vec![token_in_ctx.token_in_h160, token_out_ctx.token_out_h160],
+            token_out_ctx.path.to_owned(),
             quant.variables.account_address_h160,
             gas_tx_ctx.tx_deadline_u256,
         )
     }
 
-    pub fn swap_non_deflationary_tokens_contract_call<T>(
+    // swap the native coin for tokens
+    // the [amount_of_token_in] is sent as the value of the transaction
+    pub fn swap_eth_for_tokens_tx<T>(
         ctx: &TradeContext<T>,
         token_in_ctx: &TokenInCTx,
         token_out_ctx: &TokenOutCTx,
         gas_tx_ctx: &GasTxCtx,
         quant: &Quant,
-    ) -> ContractCall<SignerMiddleware<Provider<Ws>, LocalWallet>, Vec<U256>>
+        is_deflationary_token: bool,
+    ) -> TransactionRequest
     where
         T: TradeSchemeVariant,
     {
+        let swap_tx = if is_deflationary_token {
+            ctx.contracts
+                .router
+                .swap_exact_eth_for_tokens_supporting_fee_on_transfer_tokens(
+                    token_out_ctx.amount_out_min_u256,
+                    token_out_ctx.path.to_owned(),
+                    quant.variables.account_address_h160,
+                    gas_tx_ctx.tx_deadline_u256,
+                )
+                .tx
+        } else {
+            ctx.contracts
+                .router
+                .swap_exact_eth_for_tokens(
+                    token_out_ctx.amount_out_min_u256,
+                    token_out_ctx.path.to_owned(),
+                    quant.variables.account_address_h160,
+                    gas_tx_ctx.tx_deadline_u256,
+                )
+                .tx
+        };
+
+        swap_tx.value(token_in_ctx.amount_of_token_in_u256)
+    }
+
+    // swap tokens for the native coin
+    pub fn swap_tokens_for_eth_tx<T>(
+        ctx: &TradeContext<T>,
+        token_in_ctx: &TokenInCTx,
+        token_out_ctx: &TokenOutCTx,
+        gas_tx_ctx: &GasTxCtx,
+        quant: &Quant,
+        is_deflationary_token: bool,
+    ) -> TransactionRequest
+    where
+        T: TradeSchemeVariant,
+    {
+        if is_deflationary_token {
+            ctx.contracts
+                .router
+                .swap_exact_tokens_for_eth_supporting_fee_on_transfer_tokens(
+                    token_in_ctx.amount_of_token_in_u256,
+                    token_out_ctx.amount_out_min_u256,
+                    token_out_ctx.path.to_owned(),
+                    quant.variables.account_address_h160,
+                    gas_tx_ctx.tx_deadline_u256,
+                )
+                .tx
+        } else {
+            ctx.contracts
+                .router
+                .swap_exact_tokens_for_eth(
+                    token_in_ctx.amount_of_token_in_u256,
+                    token_out_ctx.amount_out_min_u256,
+                    token_out_ctx.path.to_owned(),
+                    quant.variables.account_address_h160,
+                    gas_tx_ctx.tx_deadline_u256,
+                )
+                .tx
+        }
+    }
+
+    // swap for an exact amount of [token_out] spending at most [amount_in_max]
+    // the unspent native coin is refunded by the router
+    pub fn swap_for_exact_tokens_tx<T>(
+        ctx: &TradeContext<T>,
+        token_in_ctx: &TokenInCTx,
+        token_out_ctx: &TokenOutCTx,
+        exact_output_ctx: &ExactOutputCTx,
+        gas_tx_ctx: &GasTxCtx,
+        quant: &Quant,
+    ) -> TransactionRequest
+    where
+        T: TradeSchemeVariant,
+    {
+        let native_token_h160 = quant.variables.native_token_h160;
+
+        if token_in_ctx.token_in_h160 == native_token_h160 {
+            return ctx
+                .contracts
+                .router
+                .swap_eth_for_exact_tokens(
+                    token_out_ctx.amount_out_max_u256,
+                    token_out_ctx.path.to_owned(),
+                    quant.variables.account_address_h160,
+                    gas_tx_ctx.tx_deadline_u256,
+                )
+                .tx
+                .value(exact_output_ctx.amount_in_max_u256);
+        }
+
+        if token_out_ctx.token_out_h160 == native_token_h160 {
+            return ctx
+                .contracts
+                .router
+                .swap_tokens_for_exact_eth(
+                    token_out_ctx.amount_out_max_u256,
+                    exact_output_ctx.amount_in_max_u256,
+                    token_out_ctx.path.to_owned(),
+                    quant.variables.account_address_h160,
+                    gas_tx_ctx.tx_deadline_u256,
+                )
+                .tx;
+        }
+
         ctx.contracts
             .router
-            .swap_exact_tokens_for_tokens_supporting_fee_on_transfer_tokens(
-                token_in_ctx.amount_of_token_in_u256,
-                token_out_ctx.amount_out_min_u256,
-                vec![token_in_ctx.token_in_h160, token_out_ctx.token_out_h160],
+            .swap_tokens_for_exact_tokens(
+                token_out_ctx.amount_out_max_u256,
+                exact_output_ctx.amount_in_max_u256,
+                token_out_ctx.path.to_owned(),
                 quant.variables.account_address_h160,
                 gas_tx_ctx.tx_deadline_u256,
             )
+            .tx
+    }
+
+    // wait until the next block is mined
+    pub async fn wait_for_next_block(quant: &Quant) -> anyhow::Result<()> {
+        let mut block_stream = quant.provider.ws.subscribe_blocks().await?;
+
+        if let Some(block) = block_stream.next().await {
+            log::debug!("received a new block: {:?}", block.number);
+        }
+
+        Ok(())
+    }
+
+    // 'eth_call' of [tx] at the latest block, nothing is sent to the network
+    // [Middleware::call] of the pinned ethers doesn't take the state overrides, the request is sent to the node directly then
+    pub async fn simulated_call(
+        quant: &Quant,
+        tx: &TransactionRequest,
+        state_override: Option<&StateOverride>,
+    ) -> anyhow::Result<Bytes> {
+        let state_override = match state_override {
+            None => return Ok(quant.middleware.client.call(tx, None).await?),
+            Some(d) => d,
+        };
+
+        let ws: &Ws = quant.middleware.client.provider().as_ref();
+        let r: Bytes = ws
+            .request("eth_call", (tx, "latest", state_override))
+            .await?;
+
+        Ok(r)
+    }
+
+    // simulate a swap of [amount_of_token_in] along [path] using 'eth_call', nothing is sent to the network
+    // the fee on transfer variants are used since they check the amount actually received against [amount_out_min_u256]
+    pub async fn is_simulated_swap_successful<T>(
+        ctx: &TradeContext<T>,
+        quant: &Quant,
+        token_in_ctx: &TokenInCTx,
+        path: &[Address],
+        amount_out_min_u256: U256,
+        tx_deadline_u256: U256,
+        state_override: Option<&StateOverride>,
+    ) -> bool
+    where
+        T: TradeSchemeVariant,
+    {
+        let native_token_h160 = quant.variables.native_token_h160;
+        let account_address_h160 = quant.variables.account_address_h160;
+
+        let simulated_tx: TransactionRequest = if token_in_ctx.token_in_h160 == native_token_h160 {
+            ctx.contracts
+                .router
+                .swap_exact_eth_for_tokens_supporting_fee_on_transfer_tokens(
+                    amount_out_min_u256,
+                    path.to_vec(),
+                    account_address_h160,
+                    tx_deadline_u256,
+                )
+                .tx
+                .value(token_in_ctx.amount_of_token_in_u256)
+        } else if path.last() == Some(&native_token_h160) {
+            ctx.contracts
+                .router
+                .swap_exact_tokens_for_eth_supporting_fee_on_transfer_tokens(
+                    token_in_ctx.amount_of_token_in_u256,
+                    amount_out_min_u256,
+                    path.to_vec(),
+                    account_address_h160,
+                    tx_deadline_u256,
+                )
+                .tx
+        } else {
+            ctx.contracts
+                .router
+                .swap_exact_tokens_for_tokens_supporting_fee_on_transfer_tokens(
+                    token_in_ctx.amount_of_token_in_u256,
+                    amount_out_min_u256,
+                    path.to_vec(),
+                    account_address_h160,
+                    tx_deadline_u256,
+                )
+                .tx
+        };
+
+        let simulated_tx = simulated_tx.from(account_address_h160);
+
+        match Self::simulated_call(quant, &simulated_tx, state_override).await {
+            Ok(_) => true,
+            Err(e) => {
+                log::debug!("the simulated swap reverted: {:?}", e);
+
+                false
+            }
+        }
+    }
+
+    // binary search for the largest [amount_out_min_u256] below [amount_out_upper_bound_u256] that the simulated swap still succeeds with
+    // the simulated swap is expected to succeed with a zero [amount_out_min_u256]
+    pub async fn max_simulated_amount_out<T>(
+        ctx: &TradeContext<T>,
+        quant: &Quant,
+        token_in_ctx: &TokenInCTx,
+        path: &[Address],
+        amount_out_upper_bound_u256: U256,
+        tx_deadline_u256: U256,
+        search_steps: u32,
+        state_override: Option<&StateOverride>,
+    ) -> U256
+    where
+        T: TradeSchemeVariant,
+    {
+        let mut low_u256 = U256::zero();
+        let mut high_u256 = amount_out_upper_bound_u256;
+
+        for _ in 0..search_steps {
+            let mid_u256 = low_u256.add(high_u256.sub(low_u256).div(2));
+
+            if Self::is_simulated_swap_successful(
+                ctx,
+                quant,
+                token_in_ctx,
+                path,
+                mid_u256,
+                tx_deadline_u256,
+                state_override,
+            )
+            .await
+            {
+                low_u256 = mid_u256;
+            } else {
+                high_u256 = mid_u256;
+            }
+        }
+
+        low_u256
     }
 
     pub async fn swap_tokens<T>(
         ctx: &TradeContext<T>,
         token_in_ctx: &TokenInCTx,
         token_out_ctx: &TokenOutCTx,
-        gas_tx_ctx: &GasTxCtx,
+        gas_tx_ctx: &mut GasTxCtx,
         quant: &Quant,
         is_deflationary_token: bool,
+        pending_swap: &mut Option<PendingSwapCtx>,
     ) -> anyhow::Result<TransactionReceipt>
     where
         T: TradeSchemeVariant,
     {
         log::debug!("initializing token swapping...");
 
-        let contract_call;
+        let native_token_h160 = quant.variables.native_token_h160;
 
-        if is_deflationary_token {
+        let swap_tx: TransactionRequest;
+
+        if let Some(e) = &token_out_ctx.exact_output {
+            log::debug!("found exact output swap");
+
+            swap_tx = Self::swap_for_exact_tokens_tx(
+                ctx,
+                token_in_ctx,
+                token_out_ctx,
+                e,
+                gas_tx_ctx,
+                quant,
+            )
+        } else if token_in_ctx.token_in_h160 == native_token_h160 {
+            log::debug!("found native token in, swapping the native coin for tokens");
+
+            swap_tx = Self::swap_eth_for_tokens_tx(
+                ctx,
+                token_in_ctx,
+                token_out_ctx,
+                gas_tx_ctx,
+                quant,
+                is_deflationary_token,
+            )
+        } else if token_out_ctx.token_out_h160 == native_token_h160 {
+            log::debug!("found native token out, swapping the tokens for the native coin");
+
+            swap_tx = Self::swap_tokens_for_eth_tx(
+                ctx,
+                token_in_ctx,
+                token_out_ctx,
+                gas_tx_ctx,
+                quant,
+                is_deflationary_token,
+            )
+        } else if is_deflationary_token {
             log::debug!("found deflationary token");
 
-            contract_call = Self::swap_deflationary_tokens_contract_call(
+            swap_tx = Self::swap_deflationary_tokens_contract_call(
                 ctx,
                 token_in_ctx,
                 token_out_ctx,
                 gas_tx_ctx,
                 quant,
             )
+            .tx
         } else {
             log::debug!("found non deflationary token");
 
-            contract_call = Self::swap_non_deflationary_tokens_contract_call(
+            swap_tx = Self::swap_non_deflationary_tokens_contract_call(
                 ctx,
                 token_in_ctx,
                 token_out_ctx,
                 gas_tx_ctx,
                 quant,
             )
+            .tx
+        }
+
+        // the pending swap tx of an earlier attempt could have been mined since the attempt gave up on it
+        let mut mined_swap = match pending_swap.as_ref() {
+            None => None,
+            Some(p) => Self::pending_swap_receipt(quant, p).await,
+        };
+
+        if mined_swap.is_none() {
+            // the gas is estimated only when a tx is going to be sent,
+            // the estimate reverts once the pending swap has spent the tokens
+            let gas_limit_u256 =
+                Self::tx_gas_limit(quant, &swap_tx, &gas_tx_ctx.gas_limit_ctx).await?;
+            let swap_tx = swap_tx.gas(gas_limit_u256);
+
+            Self::send_swap_tx(quant, swap_tx, gas_tx_ctx, pending_swap).await?;
+
+            if let Some(p) = pending_swap.as_ref() {
+                mined_swap =
+                    Self::wait_for_pending_swap_receipt(quant, p, gas_tx_ctx.tx_timeout_in_ms)
+                        .await;
+            }
+        }
+
+        let (r, gas_price_gwei) = match mined_swap {
+            None => {
+                return Err(TradingError::SwapToken(
+                    "the swap tokens tx wasn't mined in time, it will be replaced on the next attempt",
+                )
+                .into());
+            }
+            Some(d) => d,
+        };
+
+        // the nonce is used up once any of the txs is mined
+        *pending_swap = None;
+
+        // the mined tx could be an earlier replacement with a lower gas price
+        gas_tx_ctx.gas_price_gwei = gas_price_gwei;
+        gas_tx_ctx.gas_price = gwei_to_human_display(gas_price_gwei).to_string();
+
+        // the gas fees are spent even if the swap has failed
+        Self::record_swap_in_ledger(quant, token_in_ctx, gas_tx_ctx, &r);
+
+        match r.status {
+            None => Err(TradingError::SwapToken(
+                "the swap tokens tx status did not return anything",
+            )
+            .into()),
+            Some(s) => {
+                let status_code = s.as_u32();
+
+                // SUCCESS!!
+                if status_code == 1 {
+                    log::debug!("swap tokens tx receipt received");
+                    log::debug!("swap tokens tx receipt: {:?}", r);
+
+                    Ok(r)
+                } else {
+                    Err(
+                        TradingError::SwapToken("the swap tokens tx status returned failure")
+                            .into(),
+                    )
+                }
+            }
         }
+    }
 
-        let swap_tx = contract_call
-            .gas(gas_tx_ctx.gas_limit_u256)
-            .gas_price(gas_tx_ctx.gas_price_gwei);
+    // the gas price of the replacement of the pending swap tx
+    // returns [None] if the min required gas price of the replacement crosses the max allowed gas price
+    fn replacement_gas_price(pending_swap: &PendingSwapCtx, gas_tx_ctx: &GasTxCtx) -> Option<U256> {
+        let last_gas_price_gwei = match pending_swap.txs.last() {
+            None => return Some(gas_tx_ctx.gas_price_gwei),
+            Some(d) => d.gas_price_gwei,
+        };
+
+        let min_gas_price_gwei = last_gas_price_gwei.add(percentage_of_u256(
+            last_gas_price_gwei,
+            DefaultValues::REPLACEMENT_TX_MIN_PERC_INCREASE_GAS_PRICE,
+        ));
+
+        if gas_tx_ctx.gas_price_gwei >= min_gas_price_gwei {
+            return Some(gas_tx_ctx.gas_price_gwei);
+        }
+
+        if let Some(max_gas_price_gwei) = gas_tx_ctx.max_gas_price_gwei {
+            if min_gas_price_gwei > max_gas_price_gwei {
+                return None;
+            }
+        }
+
+        log::info!(
+            "increasing the gas price of the replacement tx to the min required: {} gwei",
+            gwei_to_human_display(min_gas_price_gwei)
+        );
+
+        Some(min_gas_price_gwei)
+    }
+
+    // send the swap tx with a new nonce, or with the nonce of the pending swap tx to replace it
+    // the pending swap tx is kept waiting if it cannot be replaced
+    async fn send_swap_tx(
+        quant: &Quant,
+        swap_tx: TransactionRequest,
+        gas_tx_ctx: &mut GasTxCtx,
+        pending_swap: &mut Option<PendingSwapCtx>,
+    ) -> anyhow::Result<()> {
+        let nonce = match pending_swap.as_ref() {
+            None => quant.middleware.next_nonce().await?,
+            Some(p) => match Self::replacement_gas_price(p, gas_tx_ctx) {
+                None => {
+                    log::warn!("the replacement tx would cross the max allowed gas price, waiting for the pending swap tx instead...");
+
+                    return Ok(());
+                }
+                Some(gas_price_gwei) => {
+                    gas_tx_ctx.gas_price_gwei = gas_price_gwei;
+                    gas_tx_ctx.gas_price = gwei_to_human_display(gas_price_gwei).to_string();
+
+                    log::info!("replacing the pending swap tx with the nonce: {}", p.nonce);
+
+                    p.nonce
+                }
+            },
+        };
+
+        let swap_tx = swap_tx.gas_price(gas_tx_ctx.gas_price_gwei).nonce(nonce);
 
         log::debug!("attempting to send the swap tokens transaction...");
 
         let pending_tx = quant
             .middleware
             .client
-            .send_transaction(swap_tx.tx, None)
+            .send_transaction(swap_tx, None)
             .await;
 
         match pending_tx {
@@ -639,56 +1922,172 @@ impl<'a> QuantTradeHelper {
                 let tx_url = get_tx_hash_url(tx_hash, quant.variables.network_name.clone());
 
                 log::info!("tx hash ({:?}) {}", tx_hash, tx_url);
-                log::debug!("waiting for the tx receipt...");
 
-                let tx_receipt_call = &t.await;
-                match tx_receipt_call {
-                    Ok(tx_receipt) => match tx_receipt {
-                        Some(r) => match r.status {
-                            None => {
-                                return Err(TradingError::SwapToken(
-                                    "the swap tokens tx status did not return anything",
-                                )
-                                .into());
-                            }
-                            Some(s) => {
-                                let status_code = s.as_u32();
-
-                                // SUCCESS!!
-                                if status_code == 1 {
-                                    log::debug!("swap tokens tx receipt received");
-                                    log::debug!("swap tokens tx receipt: {:?}", r);
-
-                                    Ok(r.clone())
-                                } else {
-                                    return Err(TradingError::SwapToken(
-                                        "the swap tokens tx status returned failure",
-                                    )
-                                    .into());
-                                }
-                            }
-                        },
-                        None => {
-                            return Err(TradingError::SwapToken(
-                                "the swap tokens tx receipt did not return anything",
-                            )
-                            .into());
-                        }
-                    },
-                    Err(e) => {
-                        let err_text = "swap tokens tx receipt returned an error";
-                        log::error!("{:?}", e);
+                pending_swap
+                    .get_or_insert(PendingSwapCtx { nonce, txs: vec![] })
+                    .txs
+                    .push(PendingSwapTxCtx {
+                        tx_hash,
+                        gas_price_gwei: gas_tx_ctx.gas_price_gwei,
+                    });
 
-                        return Err(TradingError::SwapToken(err_text).into());
-                    }
-                }
+                Ok(())
             }
             Err(e) => {
                 let err_text = "some error occured while sending the swap tokens transaction";
                 log::error!("{:?}", e);
 
-                return Err(TradingError::SwapToken(err_text).into());
+                // the nonce of a new tx that wasn't sent is handed out again
+                if pending_swap.is_none() {
+                    quant.middleware.reset_nonce().await;
+                }
+
+                Err(TradingError::SwapToken(err_text).into())
             }
         }
     }
+
+    // the receipt of the mined tx of the pending swap along with its gas price
+    async fn pending_swap_receipt(
+        quant: &Quant,
+        pending_swap: &PendingSwapCtx,
+    ) -> Option<(TransactionReceipt, U256)> {
+        for (i, tx) in pending_swap.txs.iter().enumerate() {
+            let tx_receipt = match quant
+                .middleware
+                .client
+                .get_transaction_receipt(tx.tx_hash)
+                .await
+            {
+                Ok(Some(d)) => d,
+                Ok(None) => continue,
+                Err(e) => {
+                    log::debug!("unable to fetch the swap tokens tx receipt: {:?}", e);
+
+                    continue;
+                }
+            };
+
+            if i == 0 {
+                log::info!("the swap tokens tx ({:?}) was mined", tx.tx_hash);
+            } else {
+                log::info!(
+                    "the replacement #{} of the swap tokens tx ({:?}) was mined",
+                    i,
+                    tx.tx_hash
+                );
+            }
+
+            return Some((tx_receipt, tx.gas_price_gwei));
+        }
+
+        None
+    }
+
+    // the pending swap tx could still be mined after the trade has given up on it
+    pub fn warn_pending_swap(pending_swap: &Option<PendingSwapCtx>) {
+        if let Some(p) = pending_swap {
+            let tx_hashes: Vec<H256> = p.txs.iter().map(|t| t.tx_hash).collect();
+
+            log::warn!(
+                "the swap tokens tx with the nonce {} is still pending and could be mined later: {:?}",
+                p.nonce,
+                tx_hashes
+            );
+        }
+    }
+
+    // wait for any of the txs of the pending swap to be mined
+    // the swap reverts past [tx_timeout_in_ms], so the tx is replaced on the next attempt if it isn't mined by then
+    // returns [None] if none of them is mined within the timeout
+    async fn wait_for_pending_swap_receipt(
+        quant: &Quant,
+        pending_swap: &PendingSwapCtx,
+        tx_timeout_in_ms: i64,
+    ) -> Option<(TransactionReceipt, U256)> {
+        log::debug!("waiting for the tx receipt...");
+
+        let wait_start_time = Instant::now();
+
+        loop {
+            if let Some(d) = Self::pending_swap_receipt(quant, pending_swap).await {
+                return Some(d);
+            }
+
+            if get_elapsed_time_ms(&wait_start_time) >= max!(tx_timeout_in_ms, 0) as u128 {
+                log::warn!(
+                    "the swap tokens tx with the nonce {} wasn't mined within {} ms",
+                    pending_swap.nonce,
+                    tx_timeout_in_ms
+                );
+
+                return None;
+            }
+
+            tokio_sleep(DefaultValues::PENDING_SWAP_TX_POLLING_INTERVAL_IN_MS).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount_in_ctx(
+        slippage: u64,
+        perc_increase_slippage: Option<u32>,
+        max_slippage: Option<u8>,
+    ) -> AmountInCtx {
+        AmountInCtx {
+            amount_of_token_in: "1".to_string(),
+            amount_of_token_in_u256: U256::exp10(18),
+            slippage,
+            perc_increase_slippage,
+            max_slippage,
+            token_in_contract: Default::default(),
+            token_in_h160: Address::zero(),
+            token_in_symbol: "WBNB".to_string(),
+            token_in_decimals: 18,
+        }
+    }
+
+    #[test]
+    fn slippage_of_the_first_attempt_isnt_increased() {
+        let ctx = amount_in_ctx(10, Some(50), Some(30));
+
+        assert_eq!(QuantTradeHelper::slippage(&ctx, 0), 10);
+        assert_eq!(QuantTradeHelper::slippage(&ctx, 1), 10);
+    }
+
+    #[test]
+    fn slippage_is_increased_on_every_retry_attempt() {
+        let ctx = amount_in_ctx(10, Some(50), None);
+
+        assert_eq!(QuantTradeHelper::slippage(&ctx, 2), 15);
+        assert_eq!(QuantTradeHelper::slippage(&ctx, 3), 20);
+        assert_eq!(QuantTradeHelper::slippage(&ctx, 5), 30);
+    }
+
+    #[test]
+    fn slippage_isnt_increased_without_perc_increase_slippage() {
+        let ctx = amount_in_ctx(10, None, Some(30));
+
+        assert_eq!(QuantTradeHelper::slippage(&ctx, 5), 10);
+    }
+
+    #[test]
+    fn slippage_is_capped_at_max_slippage() {
+        let ctx = amount_in_ctx(10, Some(50), Some(18));
+
+        assert_eq!(QuantTradeHelper::slippage(&ctx, 2), 15);
+        assert_eq!(QuantTradeHelper::slippage(&ctx, 3), 18);
+        assert_eq!(QuantTradeHelper::slippage(&ctx, 10), 18);
+    }
+
+    #[test]
+    fn slippage_is_capped_at_100_perc_without_max_slippage() {
+        let ctx = amount_in_ctx(50, Some(100), None);
+
+        assert_eq!(QuantTradeHelper::slippage(&ctx, 3), 100);
+    }
 }
@@ -0,0 +1,224 @@
+use crate::common::errors::TransferError;
+use crate::common::helpers::ethers::get_network_gas_price;
+use crate::common::helpers::urls::get_tx_hash_url;
+use crate::common::utils::ether::units_to_human_display;
+use crate::controllers::cli::entry_points::EntryPoints;
+use crate::controllers::quant::quant::{Quant, QuantTokenTransfer, TransferContext};
+use crate::features::trade_helper::QuantTradeHelper;
+use ethers::contract::builders::ContractCall;
+use ethers::core::types::TransactionReceipt;
+use ethers::middleware::SignerMiddleware;
+use ethers::prelude::{LocalWallet, Middleware, Provider, Ws};
+use ethers::types::U256;
+
+pub struct QuantTransfer {
+    pub transaction_receipts: Vec<TransactionReceipt>,
+}
+
+impl QuantTransfer {
+    fn print_info(ctx: &TransferContext, amount_of_tokens_u256: U256, quant: &Quant) {
+        log::debug!("Transfer details:");
+
+        log::info!(
+            "Token: {} ({})",
+            ctx.scheme.token_symbol,
+            ctx.scheme.token_contract
+        );
+        log::info!(
+            "Recipient: {} ({:?})",
+            ctx.scheme.wallet_name,
+            ctx.recipient_wallet_address_h160
+        );
+        log::info!(
+            "Amount of tokens: {}({})",
+            units_to_human_display(amount_of_tokens_u256, ctx.token_decimals),
+            ctx.scheme.token_symbol
+        );
+        log::info!("Account address: {}", quant.variables.account_address);
+
+        if let Some(gas_price) = &ctx.scheme.gas_price {
+            log::info!("Gas Price (GWEI): {}", gas_price);
+        }
+
+        if let Some(gas_limit) = &ctx.scheme.gas_limit {
+            log::info!("Gas Limit: {}", gas_limit);
+        }
+    }
+
+    // check whether the account has enough tokens to carry out the transfer
+    async fn check_token_balance(
+        ctx: &TransferContext,
+        q: &Quant,
+        amount_of_tokens_u256: U256,
+    ) -> anyhow::Result<()> {
+        log::debug!("checking the {} balance...", ctx.scheme.token_symbol);
+
+        let erc20_balance_of_contract_call: ContractCall<
+            SignerMiddleware<Provider<_>, LocalWallet>,
+            U256,
+        > = ctx.token_erc20.balance_of(q.variables.account_address_h160);
+
+        let token_balance_u256 = erc20_balance_of_contract_call.call().await?;
+
+        log::debug!(
+            "total available {} balance in the account: {}",
+            ctx.scheme.token_symbol,
+            units_to_human_display(token_balance_u256, ctx.token_decimals)
+        );
+
+        if token_balance_u256 < amount_of_tokens_u256 {
+            return Err(TransferError::InsufficientBalance(
+                "the account does not have enough tokens to carry out the transfer",
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    async fn transfer_tokens(
+        ctx: &TransferContext,
+        q: &Quant,
+        amount_of_tokens_u256: U256,
+    ) -> anyhow::Result<TransactionReceipt> {
+        log::debug!("initializing the token transfer...");
+
+        let mut transfer_contract_call: ContractCall<
+            SignerMiddleware<Provider<Ws>, LocalWallet>,
+            bool,
+        > = ctx
+            .token_erc20
+            .transfer(ctx.recipient_wallet_address_h160, amount_of_tokens_u256);
+
+        if let Some(gas_limit_u256) = ctx.scheme.gas_limit_u256()? {
+            transfer_contract_call = transfer_contract_call.gas(gas_limit_u256);
+        }
+
+        // the network gas price is set up front so that the gas fees of the transfer can be recorded in the ledger
+        let gas_price_gwei = match ctx.scheme.gas_price_gwei()? {
+            Some(d) => d,
+            None => get_network_gas_price(&q.middleware.client).await?,
+        };
+        transfer_contract_call = transfer_contract_call.gas_price(gas_price_gwei);
+
+        log::debug!("attempting to send the token transfer transaction...");
+
+        let nonce = q.middleware.next_nonce().await?;
+
+        let pending_tx = q
+            .middleware
+            .client
+            .send_transaction(transfer_contract_call.tx.nonce(nonce), None)
+            .await;
+
+        match pending_tx {
+            Ok(t) => {
+                log::debug!("waiting for the tx receipt...");
+
+                let tx_receipt_call = &t.await;
+
+                if let Ok(Some(r)) = tx_receipt_call {
+                    QuantTradeHelper::record_gas_in_ledger(
+                        q,
+                        ctx.token_erc20.address(),
+                        gas_price_gwei,
+                        r,
+                    );
+                }
+
+                match tx_receipt_call {
+                    Ok(tx_receipt) => match tx_receipt {
+                        Some(r) => match r.status {
+                            None => Err(TransferError::SendToken(
+                                "the token transfer tx status did not return anything",
+                            )
+                            .into()),
+                            Some(s) => {
+                                let status_code = s.as_u32();
+
+                                if status_code == 1 {
+                                    log::debug!("token transfer tx receipt received");
+                                    log::debug!("token transfer tx receipt: {:?}", r);
+
+                                    Ok(r.clone())
+                                } else {
+                                    Err(TransferError::SendToken(
+                                        "the token transfer tx status returned failure",
+                                    )
+                                    .into())
+                                }
+                            }
+                        },
+                        None => Err(TransferError::SendToken(
+                            "the token transfer tx receipt did not return anything",
+                        )
+                        .into()),
+                    },
+                    Err(e) => {
+                        let err_text = "token transfer tx receipt returned an error";
+                        log::error!("{:?}", e);
+
+                        Err(TransferError::SendToken(err_text).into())
+                    }
+                }
+            }
+            Err(e) => {
+                let err_text = "some error occured while sending the token transfer transaction";
+                log::error!("{:?}", e);
+
+                q.middleware.reset_nonce().await;
+
+                Err(TransferError::SendToken(err_text).into())
+            }
+        }
+    }
+
+    pub async fn new(
+        q: &Quant,
+        entry_points: &EntryPoints,
+        transfer_ctx: &QuantTokenTransfer,
+    ) -> anyhow::Result<Self> {
+        log::debug!("initializing the 'Token Transfer' function...");
+
+        let mut transaction_receipts: Vec<TransactionReceipt> = vec![];
+
+        for ctx in &transfer_ctx.transfer_context {
+            let amount_of_tokens_u256 = ctx.scheme.amount_of_token_u256(ctx.token_decimals)?;
+
+            log::debug!("\n");
+            Self::print_info(ctx, amount_of_tokens_u256, q);
+            log::debug!("\n\n\n");
+
+            Self::check_token_balance(ctx, q, amount_of_tokens_u256).await?;
+
+            if entry_points.dry_run {
+                log::info!("dry run successfull...");
+                log::debug!("\n\n\n");
+
+                continue;
+            }
+
+            let r = Self::transfer_tokens(ctx, q, amount_of_tokens_u256).await?;
+
+            log::info!(
+                "YAY!!! {} {} were transferred to '{}'!",
+                ctx.scheme.amount_of_tokens,
+                ctx.scheme.token_symbol,
+                ctx.scheme.wallet_name
+            );
+            log::info!(
+                "tx hash ({:?}) {}",
+                r.transaction_hash,
+                get_tx_hash_url(r.transaction_hash, q.variables.network_name.clone())
+            );
+
+            transaction_receipts.push(r);
+        }
+
+        log::debug!("finishing up the Token Transfer");
+
+        Ok(QuantTransfer {
+            transaction_receipts,
+        })
+    }
+}
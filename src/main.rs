@@ -13,10 +13,13 @@ use crate::common::helpers::parsers::setting_files::SettingFiles;
 use crate::common::utils::logs::fern_log::setup_logging;
 use crate::controllers::cli::entry_points::EntryPoints;
 use crate::controllers::quant::quant::{Quant, QuantFeature};
+use crate::features::cancel_tx::QuantCancelTx;
 use crate::features::trade::QuantTrade;
+use crate::features::transfer::QuantTransfer;
 use dialoguer::console::Term;
 use dialoguer::theme::ColorfulTheme;
 use dialoguer::Select;
+use std::sync::Arc;
 
 mod common;
 mod controllers;
@@ -41,9 +44,10 @@ fn main() {
 
 async fn run() -> anyhow::Result<()> {
     let settings = SettingFiles::new();
-    let entry_points = EntryPoints::new()?;
+    let entry_points = Arc::new(EntryPoints::new()?);
 
-    let quant = Quant::new(&settings, &entry_points).await?;
+    // [quant] and [entry_points] are shared across the trade tasks
+    let quant = Arc::new(Quant::new(&settings, &entry_points).await?);
 
     if entry_points.config_check {
         log::info!("config check was successful, exiting...");
@@ -69,11 +73,17 @@ async fn run() -> anyhow::Result<()> {
     }
 
     match &quant.feature {
-        QuantFeature::TokenTransfers(token_transfer_ctx) => {}
+        QuantFeature::TokenTransfers(token_transfer_ctx) => {
+            QuantTransfer::new(&quant, &entry_points, token_transfer_ctx).await?;
+        }
 
         QuantFeature::Trading(trading_ctx) => {
             QuantTrade::new(&quant, &entry_points, trading_ctx).await?;
         }
+
+        QuantFeature::CancelTx => {
+            QuantCancelTx::new(&quant, &entry_points).await?;
+        }
     }
 
     Ok(())